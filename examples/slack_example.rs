@@ -23,7 +23,6 @@
 // to.
 //
 
-use slack;
 use slack::{Event, RtmClient};
 
 struct MyHandler;
@@ -48,7 +47,7 @@ impl slack::EventHandler for MyHandler {
                 .expect("general channel not found");
             let _ = cli
                 .sender()
-                .send_message(&general_channel_id, "Hello world! (rtm)");
+                .send_message(general_channel_id, "Hello world! (rtm)");
             // Send a message over the real time api websocket
         }
     }
@@ -0,0 +1,78 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub use slack_api::sync::auth::*;
+
+use crate::api::call;
+use crate::api::requests::SlackWebRequestSender;
+use crate::error::Error;
+
+/// Shadows `slack_api`'s version to add `bot_id`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct TestResponse {
+    pub url: Option<String>,
+    pub team: Option<String>,
+    pub user: Option<String>,
+    pub team_id: Option<String>,
+    pub user_id: Option<String>,
+    /// Set when the token belongs to a bot user
+    pub bot_id: Option<String>,
+}
+
+/// Checks the token and tells who it belongs to, without opening a
+/// websocket like `rtm.start` does.
+///
+/// A rejected token is an `Error::Slack` whose `auth_failure` is the code.
+///
+/// Wraps https://api.slack.com/methods/auth.test
+pub fn test<R>(client: &R, token: &str) -> Result<TestResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    call(client, "auth.test", &[("token", token)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mock::MockSender;
+
+    #[test]
+    fn test_bot_token() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "url": "https://subarachnoid.slack.com/",
+            "team": "Subarachnoid Workspace",
+            "user": "grace",
+            "team_id": "T12345678",
+            "user_id": "W12345678",
+            "bot_id": "B0AB12CDE",
+            "is_enterprise_install": false
+        }"#]);
+        let response = test(&client, "xoxb-token").unwrap();
+        assert_eq!(response.user_id.unwrap(), "W12345678");
+        assert_eq!(response.bot_id.unwrap(), "B0AB12CDE");
+        assert_eq!(client.requests()[0].0, "https://slack.com/api/auth.test");
+        assert_eq!(client.param(0, "token").unwrap(), "xoxb-token");
+    }
+
+    #[test]
+    fn test_invalid_token() {
+        let client = MockSender::new(&[r#"{"ok": false, "error": "invalid_auth"}"#]);
+        let err = test(&client, "xoxb-bad").unwrap_err();
+        assert_eq!(err.auth_failure(), Some("invalid_auth"));
+    }
+}
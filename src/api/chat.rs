@@ -0,0 +1,836 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+pub use slack_api::sync::chat::*;
+
+use crate::api::requests::SlackWebRequestSender;
+use crate::api::{call, flag, HttpClient, Message, Status, Timestamp};
+use crate::error::Error;
+use crate::MessageExt;
+use std::error;
+use std::fmt;
+
+#[derive(Clone, Default, Debug)]
+pub struct GetPermalinkRequest<'a> {
+    /// The id of the conversation containing the message.
+    pub channel: &'a str,
+    /// The message's `ts` value.
+    pub message_ts: Timestamp,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct GetPermalinkResponse {
+    pub channel: Option<String>,
+    pub permalink: String,
+}
+
+/// Retrieves a permalink URL for a specific extant message.
+///
+/// Wraps https://api.slack.com/methods/chat.getPermalink
+pub fn get_permalink<R>(
+    client: &R,
+    token: &str,
+    request: &GetPermalinkRequest<'_>,
+) -> Result<GetPermalinkResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let message_ts = request.message_ts.to_param_value();
+    let params = [
+        ("token", token),
+        ("channel", request.channel),
+        ("message_ts", &message_ts[..]),
+    ];
+    call(client, "chat.getPermalink", &params)
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct PostEphemeralRequest<'a> {
+    /// Channel, private group, or IM channel to send message to.
+    pub channel: &'a str,
+    /// The user who will see the message. Must be a member of `channel`.
+    pub user: &'a str,
+    /// Text of the message to send.
+    pub text: &'a str,
+    /// Structured message attachments, as a JSON array.
+    pub attachments: Option<&'a str>,
+    /// Pass true to post the message as the authed user.
+    pub as_user: Option<bool>,
+    /// Find and link channel names and usernames.
+    pub link_names: Option<bool>,
+    /// Change how messages are treated.
+    pub parse: Option<&'a str>,
+    /// Post the ephemeral message inside this thread.
+    pub thread_ts: Option<Timestamp>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PostEphemeralResponse {
+    pub message_ts: Option<Timestamp>,
+}
+
+/// Sends an ephemeral message to a user in a channel.
+///
+/// Wraps https://api.slack.com/methods/chat.postEphemeral
+pub fn post_ephemeral<R>(
+    client: &R,
+    token: &str,
+    request: &PostEphemeralRequest<'_>,
+) -> Result<PostEphemeralResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let thread_ts = request.thread_ts.map(|t| t.to_param_value());
+    let params = vec![
+        Some(("token", token)),
+        Some(("channel", request.channel)),
+        Some(("user", request.user)),
+        Some(("text", request.text)),
+        request
+            .attachments
+            .map(|attachments| ("attachments", attachments)),
+        request.as_user.map(|as_user| ("as_user", flag(as_user))),
+        request
+            .link_names
+            .map(|link_names| ("link_names", flag(link_names))),
+        request.parse.map(|parse| ("parse", parse)),
+        thread_ts
+            .as_ref()
+            .map(|thread_ts| ("thread_ts", &thread_ts[..])),
+    ];
+    let params = params.into_iter().flatten().collect::<Vec<_>>();
+    call(client, "chat.postEphemeral", &params)
+}
+
+/// Posts plain `text` to `channel`, or in the thread `thread_ts` if given,
+/// and returns the new message's `ts`, e.g. to start a thread under it.
+pub fn post_text<R>(
+    client: &R,
+    token: &str,
+    channel: &str,
+    text: &str,
+    thread_ts: Option<Timestamp>,
+) -> Result<Timestamp, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let request = PostMessageRequest {
+        channel,
+        text,
+        thread_ts,
+        ..Default::default()
+    };
+    post_message(client, token, &request)?
+        .ts
+        .ok_or_else(|| Error::Internal("Slack did not return the posted message's ts".into()))
+}
+
+/// A `post_chunked` that failed part way through
+#[derive(Debug)]
+pub struct PostChunkedError {
+    /// The `ts`s of the chunks posted before the failure, in order
+    pub posted: Vec<Timestamp>,
+    /// Why the next chunk could not be posted
+    pub error: Error,
+}
+
+impl fmt::Display for PostChunkedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (after {} chunks)", self.error, self.posted.len())
+    }
+}
+
+impl error::Error for PostChunkedError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<PostChunkedError> for Error {
+    fn from(err: PostChunkedError) -> Error {
+        err.error
+    }
+}
+
+/// Posts `text` to `channel` as consecutive messages of at most `limit`
+/// characters each, and returns their `ts`s in order.
+///
+/// Splits between lines where it can, and within a line only if the line
+/// alone is over `limit`. Slack shortens the display of messages over about
+/// 4000 characters, so that is a sensible `limit`.
+///
+/// Stops at the first chunk that fails; the error keeps the `ts`s of the
+/// chunks already posted, e.g. to delete them or to post the rest.
+pub fn post_chunked<R>(
+    client: &R,
+    token: &str,
+    channel: &str,
+    text: &str,
+    limit: usize,
+) -> Result<Vec<Timestamp>, PostChunkedError>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let mut posted = Vec::new();
+    for chunk in split_chunks(text, limit) {
+        match post_text(client, token, channel, chunk, None) {
+            Ok(ts) => posted.push(ts),
+            Err(error) => return Err(PostChunkedError { posted, error }),
+        }
+    }
+    Ok(posted)
+}
+
+/// Splits `text` into pieces of at most `limit` characters, at newlines
+/// where possible, leaving out empty pieces
+fn split_chunks(text: &str, limit: usize) -> Vec<&str> {
+    let limit = limit.max(1);
+    let mut chunks = Vec::new();
+    // start, end and character count of the chunk being built
+    let mut chunk: Option<(usize, usize, usize)> = None;
+    let mut offset = 0;
+    for line in text.split('\n') {
+        let (line_start, line_end) = (offset, offset + line.len());
+        offset = line_end + 1;
+        let line_len = line.chars().count();
+        if let Some((start, end, len)) = chunk {
+            if len + 1 + line_len <= limit {
+                chunk = Some((start, line_end, len + 1 + line_len));
+                continue;
+            }
+            chunks.push(&text[start..end]);
+        }
+        // the line starts a new chunk, cut it up if it's too long by itself
+        let (mut start, mut len) = (line_start, 0);
+        for (i, _) in line.char_indices() {
+            if len == limit {
+                chunks.push(&text[start..line_start + i]);
+                start = line_start + i;
+                len = 0;
+            }
+            len += 1;
+        }
+        chunk = Some((start, line_end, len));
+    }
+    if let Some((start, end, _)) = chunk {
+        chunks.push(&text[start..end]);
+    }
+    chunks.retain(|chunk| !chunk.is_empty());
+    chunks
+}
+
+/// A response to an interaction, see `respond`
+#[derive(Clone, Default, Debug, Serialize)]
+pub struct RespondRequest<'a> {
+    /// Text of the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<&'a str>,
+    /// Structured message attachments, a JSON array.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<&'a serde_json::Value>,
+    /// Layout blocks, a JSON array.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocks: Option<&'a serde_json::Value>,
+    /// `ephemeral`, the default, to show the response only to the user who
+    /// interacted, or `in_channel` to post it for everyone.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_type: Option<&'a str>,
+    /// Replace the message the interaction came from with this one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replace_original: Option<bool>,
+    /// Delete the message the interaction came from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete_original: Option<bool>,
+}
+
+/// Responds to an interaction, such as a button click, through the
+/// `response_url` that came with it.
+///
+/// The url itself authorizes the response, so no token is sent. See
+/// https://api.slack.com/interactivity/handling#message_responses
+pub fn respond(
+    client: &HttpClient,
+    response_url: &str,
+    request: &RespondRequest<'_>,
+) -> Result<(), Error> {
+    let body = client
+        .post(response_url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(serde_json::to_string(request)?)
+        .send()?
+        .error_for_status()?
+        .text()?;
+    // Slack answers with a plain `ok` or a JSON status
+    match serde_json::from_str::<Status>(&body) {
+        Ok(Status { ok: false, error }) => Err(Error::Slack {
+            method: "response_url".into(),
+            code: error.unwrap_or_else(|| "unknown_error".into()),
+        }),
+        _ => Ok(()),
+    }
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct ScheduleMessageRequest<'a> {
+    /// Channel, private group, or IM channel to send message to.
+    pub channel: &'a str,
+    /// Unix time at which Slack should post the message.
+    pub post_at: u64,
+    /// Text of the message to send.
+    pub text: &'a str,
+    /// Structured message attachments, as a JSON array.
+    pub attachments: Option<&'a str>,
+    /// Pass true to post the message as the authed user.
+    pub as_user: Option<bool>,
+    /// Find and link channel names and usernames.
+    pub link_names: Option<bool>,
+    /// Change how messages are treated.
+    pub parse: Option<&'a str>,
+    /// Post the message as a reply inside this thread.
+    pub thread_ts: Option<Timestamp>,
+    /// Also show a threaded reply in the channel.
+    pub reply_broadcast: Option<bool>,
+    /// Enable unfurling of primarily text-based content.
+    pub unfurl_links: Option<bool>,
+    /// Enable unfurling of media content.
+    pub unfurl_media: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ScheduleMessageResponse {
+    pub channel: String,
+    /// Id to pass to `delete_scheduled_message`
+    pub scheduled_message_id: String,
+    pub post_at: u64,
+}
+
+/// Schedules a message to be posted to a channel later.
+///
+/// Wraps https://api.slack.com/methods/chat.scheduleMessage
+pub fn schedule_message<R>(
+    client: &R,
+    token: &str,
+    request: &ScheduleMessageRequest<'_>,
+) -> Result<ScheduleMessageResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let post_at = request.post_at.to_string();
+    let thread_ts = request.thread_ts.map(|t| t.to_param_value());
+    let params = vec![
+        Some(("token", token)),
+        Some(("channel", request.channel)),
+        Some(("post_at", &post_at[..])),
+        Some(("text", request.text)),
+        request
+            .attachments
+            .map(|attachments| ("attachments", attachments)),
+        request.as_user.map(|as_user| ("as_user", flag(as_user))),
+        request
+            .link_names
+            .map(|link_names| ("link_names", flag(link_names))),
+        request.parse.map(|parse| ("parse", parse)),
+        thread_ts
+            .as_ref()
+            .map(|thread_ts| ("thread_ts", &thread_ts[..])),
+        request
+            .reply_broadcast
+            .map(|reply_broadcast| ("reply_broadcast", flag(reply_broadcast))),
+        request
+            .unfurl_links
+            .map(|unfurl_links| ("unfurl_links", flag(unfurl_links))),
+        request
+            .unfurl_media
+            .map(|unfurl_media| ("unfurl_media", flag(unfurl_media))),
+    ];
+    let params = params.into_iter().flatten().collect::<Vec<_>>();
+    call(client, "chat.scheduleMessage", &params)
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct DeleteScheduledMessageRequest<'a> {
+    /// The channel the message is scheduled in.
+    pub channel: &'a str,
+    /// The `scheduled_message_id` returned by `schedule_message`.
+    pub scheduled_message_id: &'a str,
+    /// Pass true to delete the message as the authed user.
+    pub as_user: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct DeleteScheduledMessageResponse {}
+
+/// Deletes a pending scheduled message.
+///
+/// Wraps https://api.slack.com/methods/chat.deleteScheduledMessage
+pub fn delete_scheduled_message<R>(
+    client: &R,
+    token: &str,
+    request: &DeleteScheduledMessageRequest<'_>,
+) -> Result<DeleteScheduledMessageResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let params = vec![
+        Some(("token", token)),
+        Some(("channel", request.channel)),
+        Some(("scheduled_message_id", request.scheduled_message_id)),
+        request.as_user.map(|as_user| ("as_user", flag(as_user))),
+    ];
+    let params = params.into_iter().flatten().collect::<Vec<_>>();
+    call(client, "chat.deleteScheduledMessage", &params)
+}
+
+/// Posts `text` as a reply in the thread `msg` belongs to, or starts a
+/// thread under `msg` if it isn't part of one yet.
+pub fn reply_in_thread<R>(
+    client: &R,
+    token: &str,
+    msg: &Message,
+    text: &str,
+) -> Result<PostMessageResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let channel = msg
+        .channel()
+        .ok_or_else(|| Error::Internal("Message has no channel to reply in".into()))?;
+    let thread_ts = msg
+        .thread_ts()
+        .or_else(|| msg.ts())
+        .ok_or_else(|| Error::Internal("Message has no ts to reply to".into()))?;
+    let request = PostMessageRequest {
+        channel,
+        text,
+        thread_ts: Some(thread_ts),
+        ..Default::default()
+    };
+    Ok(post_message(client, token, &request)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mock::MockSender;
+
+    #[test]
+    fn get_permalink() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "channel": "C1H9RESGA",
+            "permalink": "https://ghostbusters.slack.com/archives/C1H9RESGA/p135854651500008"
+        }"#]);
+        let response = super::get_permalink(
+            &client,
+            "xoxb-token",
+            &GetPermalinkRequest {
+                channel: "C1H9RESGA",
+                message_ts: Timestamp::from((1358546515, 0.000008)),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            response.permalink,
+            "https://ghostbusters.slack.com/archives/C1H9RESGA/p135854651500008"
+        );
+        assert_eq!(client.param(0, "message_ts").unwrap(), "1358546515.000008");
+    }
+
+    #[test]
+    fn post_ephemeral_returns_message_ts() {
+        let client = MockSender::new(&[r#"{"ok": true, "message_ts": "1502210682.580145"}"#]);
+        let response = post_ephemeral(
+            &client,
+            "xoxb-token",
+            &PostEphemeralRequest {
+                channel: "C1234567890",
+                user: "U0BPQUNTA",
+                text: "Only you can see this",
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            response.message_ts.unwrap().to_param_value(),
+            "1502210682.580145"
+        );
+        let requests = client.requests();
+        assert_eq!(requests[0].0, "https://slack.com/api/chat.postEphemeral");
+        assert_eq!(client.param(0, "user").unwrap(), "U0BPQUNTA");
+        assert_eq!(client.param(0, "channel").unwrap(), "C1234567890");
+        assert!(client.param(0, "attachments").is_none());
+    }
+
+    #[test]
+    fn post_ephemeral_error() {
+        let client = MockSender::new(&[r#"{"ok": false, "error": "user_not_in_channel"}"#]);
+        let err = post_ephemeral(
+            &client,
+            "xoxb-token",
+            &PostEphemeralRequest {
+                channel: "C1234567890",
+                user: "U0BPQUNTA",
+                text: "Only you can see this",
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), Some("user_not_in_channel"));
+    }
+
+    #[test]
+    fn post_message_unfurl_control() {
+        let response = r#"{"ok": true, "channel": "C1234567890", "ts": "1502210682.580145"}"#;
+        let client = MockSender::new(&[response, response]);
+        let mut request = PostMessageRequest {
+            channel: "C1234567890",
+            text: "https://example.com/build/42",
+            unfurl_links: Some(false),
+            unfurl_media: Some(false),
+            ..Default::default()
+        };
+        post_message(&client, "xoxb-token", &request).unwrap();
+        assert_eq!(client.param(0, "unfurl_links").unwrap(), "0");
+        assert_eq!(client.param(0, "unfurl_media").unwrap(), "0");
+
+        request.unfurl_links = None;
+        request.unfurl_media = None;
+        post_message(&client, "xoxb-token", &request).unwrap();
+        assert!(client.param(1, "unfurl_links").is_none());
+        assert!(client.param(1, "unfurl_media").is_none());
+    }
+
+    #[test]
+    fn post_message_custom_persona() {
+        let response = r#"{"ok": true, "channel": "C1234567890", "ts": "1502210682.580145"}"#;
+        let client = MockSender::new(&[response, response]);
+        let mut request = PostMessageRequest {
+            channel: "C1234567890",
+            text: "Deploy finished",
+            username: Some("deploybot"),
+            icon_emoji: Some(":rocket:"),
+            icon_url: Some("https://example.com/rocket.png"),
+            ..Default::default()
+        };
+        post_message(&client, "xoxb-token", &request).unwrap();
+        assert_eq!(client.param(0, "username").unwrap(), "deploybot");
+        assert_eq!(client.param(0, "icon_emoji").unwrap(), ":rocket:");
+        assert_eq!(
+            client.param(0, "icon_url").unwrap(),
+            "https://example.com/rocket.png"
+        );
+
+        request.username = None;
+        request.icon_emoji = None;
+        request.icon_url = None;
+        post_message(&client, "xoxb-token", &request).unwrap();
+        assert!(client.param(1, "username").is_none());
+        assert!(client.param(1, "icon_emoji").is_none());
+        assert!(client.param(1, "icon_url").is_none());
+    }
+
+    #[test]
+    fn split_chunks_at_lines() {
+        assert_eq!(split_chunks("one\ntwo\nthree", 7), ["one\ntwo", "three"]);
+        assert_eq!(split_chunks("one\ntwo\nthree", 100), ["one\ntwo\nthree"]);
+        assert_eq!(split_chunks("one\n\n\ntwo\n", 3), ["one", "\n", "two"]);
+        assert_eq!(split_chunks("one\n  \ntwo", 3), ["one", "  ", "two"]);
+        assert!(split_chunks("", 10).is_empty());
+    }
+
+    #[test]
+    fn split_chunks_long_line() {
+        assert_eq!(
+            split_chunks("ab\nabcdefgh\nxy", 3),
+            ["ab", "abc", "def", "gh", "xy"]
+        );
+        // characters, not bytes
+        assert_eq!(split_chunks("ééé\nü", 3), ["ééé", "ü"]);
+        assert_eq!(split_chunks("ééé\nü", 2), ["éé", "é", "ü"]);
+    }
+
+    #[test]
+    fn post_chunked_in_order() {
+        let client = MockSender::new(&[
+            r#"{"ok": true, "channel": "C1", "ts": "1502210682.000001"}"#,
+            r#"{"ok": true, "channel": "C1", "ts": "1502210682.000002"}"#,
+        ]);
+        let ts = post_chunked(&client, "xoxb-token", "C1", "first line\nsecond line", 12).unwrap();
+        assert_eq!(ts.len(), 2);
+        assert!(ts[0] < ts[1]);
+        assert_eq!(client.param(0, "text").unwrap(), "first line");
+        assert_eq!(client.param(1, "text").unwrap(), "second line");
+        assert_eq!(client.param(1, "channel").unwrap(), "C1");
+    }
+
+    #[test]
+    fn post_chunked_keeps_posted_ts_on_error() {
+        let client = MockSender::new(&[
+            r#"{"ok": true, "channel": "C1", "ts": "1502210682.000001"}"#,
+            r#"{"ok": false, "error": "rate_limited"}"#,
+        ]);
+        let text = "first line\nsecond line\nthird line";
+        let err = post_chunked(&client, "xoxb-token", "C1", text, 12).unwrap_err();
+        assert_eq!(err.error.code(), Some("rate_limited"));
+        assert_eq!(err.posted.len(), 1);
+        assert_eq!(err.posted[0].to_string(), "1502210682.000001");
+        assert_eq!(client.requests().len(), 2);
+    }
+
+    /// Serves one HTTP request with `body`, returning the url to send it to
+    /// and the request received
+    fn response_url(body: &'static str) -> (String, std::thread::JoinHandle<String>) {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!(
+            "http://{}/actions/T1/B2/XYZ",
+            listener.local_addr().unwrap()
+        );
+        let handle = std::thread::spawn(move || {
+            let (mut conn, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            loop {
+                let n = conn.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some(end) = text.find("\r\n\r\n") {
+                    let length = text[..end]
+                        .lines()
+                        .find_map(|l| l.strip_prefix("content-length: "))
+                        .map_or(0, |l| l.parse().unwrap());
+                    if request.len() >= end + 4 + length {
+                        break;
+                    }
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            conn.write_all(response.as_bytes()).unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn respond_posts_json() {
+        let (url, handle) = response_url("ok");
+        let blocks = serde_json::json!([{"type": "section", "text": {"type": "mrkdwn", "text": "Approved"}}]);
+        let request = RespondRequest {
+            text: Some("Approved"),
+            blocks: Some(&blocks),
+            replace_original: Some(true),
+            ..Default::default()
+        };
+        respond(&HttpClient::new(), &url, &request).unwrap();
+        let received = handle.join().unwrap();
+        assert!(received.starts_with("POST /actions/T1/B2/XYZ HTTP/1.1\r\n"));
+        assert!(received.contains("content-type: application/json\r\n"));
+        let body = &received[received.find("\r\n\r\n").unwrap() + 4..];
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(body).unwrap(),
+            serde_json::json!({"text": "Approved", "blocks": blocks, "replace_original": true})
+        );
+    }
+
+    #[test]
+    fn respond_error() {
+        let (url, handle) = response_url(r#"{"ok": false, "error": "expired_url"}"#);
+        let request = RespondRequest {
+            delete_original: Some(true),
+            ..Default::default()
+        };
+        let err = respond(&HttpClient::new(), &url, &request).unwrap_err();
+        handle.join().unwrap();
+        assert_eq!(err.code(), Some("expired_url"));
+    }
+
+    fn message(json: &str) -> Message {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn reply_in_existing_thread() {
+        let client = MockSender::new(&[r#"{"ok": true, "ts": "1483037605.017509"}"#]);
+        let msg = message(
+            r#"{
+            "type": "message",
+            "channel": "C061EG9SL",
+            "user": "U061F7AUR",
+            "text": "a reply",
+            "ts": "1483037603.017503",
+            "thread_ts": "1482960137.003543"
+        }"#,
+        );
+        reply_in_thread(&client, "xoxb-token", &msg, "me too").unwrap();
+        assert_eq!(client.param(0, "channel").unwrap(), "C061EG9SL");
+        assert_eq!(client.param(0, "thread_ts").unwrap(), "1482960137.003543");
+        assert_eq!(client.param(0, "text").unwrap(), "me too");
+    }
+
+    #[test]
+    fn reply_starts_thread() {
+        let client = MockSender::new(&[r#"{"ok": true, "ts": "1483037605.017509"}"#]);
+        let msg = message(
+            r#"{
+            "type": "message",
+            "channel": "C061EG9SL",
+            "user": "U061F7AUR",
+            "text": "top level",
+            "ts": "1483037603.017503"
+        }"#,
+        );
+        reply_in_thread(&client, "xoxb-token", &msg, "on it").unwrap();
+        assert_eq!(client.param(0, "thread_ts").unwrap(), "1483037603.017503");
+    }
+
+    #[test]
+    fn post_text_returns_ts() {
+        let client = MockSender::new(&[
+            r#"{"ok": true, "channel": "C1H9RESGL", "ts": "1503435956.000247"}"#,
+            r#"{"ok": true, "channel": "C1H9RESGL", "ts": "1503435957.000100"}"#,
+        ]);
+        let ts = post_text(&client, "xoxb-token", "C1H9RESGL", "deploying", None).unwrap();
+        assert_eq!(ts.to_param_value(), "1503435956.000247");
+        assert!(client.param(0, "thread_ts").is_none());
+        post_text(&client, "xoxb-token", "C1H9RESGL", "done", Some(ts)).unwrap();
+        assert_eq!(client.param(1, "thread_ts").unwrap(), "1503435956.000247");
+    }
+
+    #[test]
+    fn post_message_returns_message() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "channel": "C1H9RESGL",
+            "ts": "1503435956.000247",
+            "message": {
+                "text": "Here's a message for you",
+                "username": "ecto1",
+                "bot_id": "B19LU7CSY",
+                "type": "message",
+                "subtype": "bot_message",
+                "ts": "1503435956.000247"
+            }
+        }"#]);
+        let response = post_message(
+            &client,
+            "xoxb-token",
+            &PostMessageRequest {
+                channel: "C1H9RESGL",
+                text: "Here's a message for you",
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let message = response.message.unwrap();
+        assert_eq!(message.text(), Some("Here's a message for you"));
+        assert_eq!(message.ts(), response.ts);
+        match message {
+            Message::BotMessage(msg) => assert_eq!(msg.bot_id.as_deref(), Some("B19LU7CSY")),
+            msg => panic!("unexpected message: {:?}", msg),
+        }
+    }
+
+    #[test]
+    fn schedule_and_delete_message() {
+        let client = MockSender::new(&[
+            r#"{
+                "ok": true,
+                "channel": "C1H9RESGL",
+                "scheduled_message_id": "Q1298393284",
+                "post_at": 1562180400,
+                "message": {
+                    "text": "Here's a message for you in the future",
+                    "username": "ecto1",
+                    "bot_id": "B19LU7CSY",
+                    "type": "delayed_message",
+                    "subtype": "bot_message"
+                }
+            }"#,
+            r#"{"ok": true}"#,
+        ]);
+        let scheduled = schedule_message(
+            &client,
+            "xoxb-token",
+            &ScheduleMessageRequest {
+                channel: "C1H9RESGL",
+                post_at: 1562180400,
+                text: "Here's a message for you in the future",
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(scheduled.scheduled_message_id, "Q1298393284");
+        assert_eq!(scheduled.post_at, 1562180400);
+        assert_eq!(
+            client.requests()[0].0,
+            "https://slack.com/api/chat.scheduleMessage"
+        );
+        assert_eq!(client.param(0, "post_at").unwrap(), "1562180400");
+
+        delete_scheduled_message(
+            &client,
+            "xoxb-token",
+            &DeleteScheduledMessageRequest {
+                channel: &scheduled.channel,
+                scheduled_message_id: &scheduled.scheduled_message_id,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            client.requests()[1].0,
+            "https://slack.com/api/chat.deleteScheduledMessage"
+        );
+        assert_eq!(
+            client.param(1, "scheduled_message_id").unwrap(),
+            "Q1298393284"
+        );
+    }
+
+    #[test]
+    fn me_message_returns_ts() {
+        let client = MockSender::new(&[
+            r#"{"ok": true, "channel": "C024BE7LR", "ts": "1417671948.000006"}"#,
+        ]);
+        let response = me_message(
+            &client,
+            "xoxb-token",
+            &MeMessageRequest {
+                channel: "C024BE7LR",
+                text: "is deploying",
+            },
+        )
+        .unwrap();
+        assert_eq!(response.ts.unwrap().to_param_value(), "1417671948.000006");
+        assert_eq!(
+            client.requests()[0].0,
+            "https://slack.com/api/chat.meMessage"
+        );
+        assert_eq!(client.param(0, "text").unwrap(), "is deploying");
+    }
+}
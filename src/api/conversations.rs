@@ -0,0 +1,1418 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! The unified `conversations.*` methods, which work on public and private
+//! channels, ims and mpims alike.
+
+use crate::api::requests::SlackWebRequestSender;
+use crate::api::{call, call_allowing, flag, ChannelPurpose, ChannelTopic, Message, Timestamp};
+use crate::error::Error;
+use crate::TimestampExt;
+
+/// A channel, private channel, im or mpim as returned by the `conversations.*` methods
+#[derive(Clone, Debug, Deserialize)]
+pub struct Conversation {
+    pub id: String,
+    pub name: Option<String>,
+    pub created: Option<i64>,
+    pub creator: Option<String>,
+    pub is_channel: Option<bool>,
+    pub is_group: Option<bool>,
+    pub is_im: Option<bool>,
+    pub is_mpim: Option<bool>,
+    pub is_private: Option<bool>,
+    pub is_archived: Option<bool>,
+    pub is_general: Option<bool>,
+    pub is_member: Option<bool>,
+    pub is_open: Option<bool>,
+    /// Shared with another workspace, in the same organization or not
+    pub is_shared: Option<bool>,
+    /// Shared with an external organization through Slack Connect
+    pub is_ext_shared: Option<bool>,
+    /// Shared between workspaces of the same Enterprise Grid organization
+    pub is_org_shared: Option<bool>,
+    /// An invitation to share the channel externally is pending
+    pub is_pending_ext_shared: Option<bool>,
+    /// Teams the channel is shared with, including the one it belongs to
+    pub shared_team_ids: Option<Vec<String>>,
+    /// The other user of an im
+    pub user: Option<String>,
+    pub last_read: Option<String>,
+    pub unread_count: Option<i32>,
+    pub num_members: Option<i32>,
+    pub topic: Option<ChannelTopic>,
+    pub purpose: Option<ChannelPurpose>,
+}
+
+/// Pagination details of a cursor-paginated response
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ResponseMetadata {
+    /// Cursor for the next page, empty or absent on the last page
+    pub next_cursor: Option<String>,
+}
+
+impl ResponseMetadata {
+    /// The cursor of the next page, if there is one
+    pub fn next_cursor(&self) -> Option<&str> {
+        self.next_cursor.as_deref().filter(|c| !c.is_empty())
+    }
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct InfoRequest<'a> {
+    /// Conversation to get info on.
+    pub channel: &'a str,
+    /// Set to true to receive the locale for this conversation.
+    pub include_locale: Option<bool>,
+    /// Set to true to include the member count for the conversation.
+    pub include_num_members: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct InfoResponse {
+    pub channel: Conversation,
+}
+
+/// Retrieves information about a conversation of any type.
+///
+/// Wraps https://api.slack.com/methods/conversations.info
+pub fn info<R>(client: &R, token: &str, request: &InfoRequest<'_>) -> Result<InfoResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let params = vec![
+        Some(("token", token)),
+        Some(("channel", request.channel)),
+        request
+            .include_locale
+            .map(|include_locale| ("include_locale", flag(include_locale))),
+        request
+            .include_num_members
+            .map(|include_num_members| ("include_num_members", flag(include_num_members))),
+    ];
+    let params = params.into_iter().flatten().collect::<Vec<_>>();
+    call(client, "conversations.info", &params)
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct MembersRequest<'a> {
+    /// Conversation to list the members of.
+    pub channel: &'a str,
+    /// Cursor returned by a previous call, to fetch the next page.
+    pub cursor: Option<&'a str>,
+    /// Maximum number of members to return per page.
+    pub limit: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MembersResponse {
+    /// User ids of the members
+    pub members: Vec<String>,
+    #[serde(default)]
+    pub response_metadata: ResponseMetadata,
+}
+
+/// Retrieves one page of members of a conversation.
+///
+/// Wraps https://api.slack.com/methods/conversations.members
+pub fn members<R>(
+    client: &R,
+    token: &str,
+    request: &MembersRequest<'_>,
+) -> Result<MembersResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let limit = request.limit.map(|limit| limit.to_string());
+    let params = vec![
+        Some(("token", token)),
+        Some(("channel", request.channel)),
+        request.cursor.map(|cursor| ("cursor", cursor)),
+        limit.as_ref().map(|limit| ("limit", &limit[..])),
+    ];
+    let params = params.into_iter().flatten().collect::<Vec<_>>();
+    call(client, "conversations.members", &params)
+}
+
+/// Retrieves all members of a conversation, following the cursor across pages.
+///
+/// `limit` is the page size used for each request.
+pub fn members_all<R>(
+    client: &R,
+    token: &str,
+    channel: &str,
+    limit: Option<u32>,
+) -> Result<Vec<String>, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let mut all = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let request = MembersRequest {
+            channel,
+            cursor: cursor.as_deref(),
+            limit,
+        };
+        let page = members(client, token, &request)?;
+        all.extend(page.members);
+        match page.response_metadata.next_cursor() {
+            Some(next) => cursor = Some(next.to_string()),
+            None => return Ok(all),
+        }
+    }
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct HistoryRequest<'a> {
+    /// Conversation to fetch the history of.
+    pub channel: &'a str,
+    /// Cursor returned by a previous call, to fetch the next page.
+    pub cursor: Option<&'a str>,
+    /// Maximum number of messages to return per page.
+    pub limit: Option<u32>,
+    /// End of the time range of messages to include.
+    pub latest: Option<Timestamp>,
+    /// Start of the time range of messages to include.
+    pub oldest: Option<Timestamp>,
+    /// Include messages with `latest` or `oldest` as their timestamp.
+    pub inclusive: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HistoryResponse {
+    /// Messages in reverse chronological order
+    #[serde(default)]
+    pub messages: Vec<Message>,
+    #[serde(default)]
+    pub has_more: bool,
+    #[serde(default)]
+    pub response_metadata: ResponseMetadata,
+}
+
+/// Retrieves one page of messages from a conversation.
+///
+/// Wraps https://api.slack.com/methods/conversations.history
+pub fn history<R>(
+    client: &R,
+    token: &str,
+    request: &HistoryRequest<'_>,
+) -> Result<HistoryResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let limit = request.limit.map(|limit| limit.to_string());
+    let latest = request.latest.map(|latest| latest.to_param_value());
+    let oldest = request.oldest.map(|oldest| oldest.to_param_value());
+    let params = vec![
+        Some(("token", token)),
+        Some(("channel", request.channel)),
+        request.cursor.map(|cursor| ("cursor", cursor)),
+        limit.as_ref().map(|limit| ("limit", &limit[..])),
+        latest.as_ref().map(|latest| ("latest", &latest[..])),
+        oldest.as_ref().map(|oldest| ("oldest", &oldest[..])),
+        request
+            .inclusive
+            .map(|inclusive| ("inclusive", flag(inclusive))),
+    ];
+    let params = params.into_iter().flatten().collect::<Vec<_>>();
+    call(client, "conversations.history", &params)
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct RepliesRequest<'a> {
+    /// Conversation the thread is in.
+    pub channel: &'a str,
+    /// The `ts` of the thread's parent message.
+    pub ts: Timestamp,
+    /// Cursor returned by a previous call, to fetch the next page.
+    pub cursor: Option<&'a str>,
+    /// Maximum number of messages to return per page.
+    pub limit: Option<u32>,
+    /// End of the time range of messages to include.
+    pub latest: Option<Timestamp>,
+    /// Start of the time range of messages to include.
+    pub oldest: Option<Timestamp>,
+    /// Include messages with `latest` or `oldest` as their timestamp.
+    pub inclusive: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct RepliesResponse {
+    /// The parent message followed by the replies, oldest first
+    #[serde(default)]
+    pub messages: Vec<Message>,
+    #[serde(default)]
+    pub has_more: bool,
+    #[serde(default)]
+    pub response_metadata: ResponseMetadata,
+}
+
+/// Retrieves one page of a thread: its parent message and replies.
+///
+/// Wraps https://api.slack.com/methods/conversations.replies
+pub fn replies<R>(
+    client: &R,
+    token: &str,
+    request: &RepliesRequest<'_>,
+) -> Result<RepliesResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let ts = request.ts.to_param_value();
+    let limit = request.limit.map(|limit| limit.to_string());
+    let latest = request.latest.map(|latest| latest.to_param_value());
+    let oldest = request.oldest.map(|oldest| oldest.to_param_value());
+    let params = vec![
+        Some(("token", token)),
+        Some(("channel", request.channel)),
+        Some(("ts", &ts[..])),
+        request.cursor.map(|cursor| ("cursor", cursor)),
+        limit.as_ref().map(|limit| ("limit", &limit[..])),
+        latest.as_ref().map(|latest| ("latest", &latest[..])),
+        oldest.as_ref().map(|oldest| ("oldest", &oldest[..])),
+        request
+            .inclusive
+            .map(|inclusive| ("inclusive", flag(inclusive))),
+    ];
+    let params = params.into_iter().flatten().collect::<Vec<_>>();
+    call(client, "conversations.replies", &params)
+}
+
+/// Fetches the parent message of the thread `thread_ts` in `channel`.
+///
+/// A thread whose parent was deleted is an `Error::Slack` with the code
+/// `thread_not_found`; an empty answer is an `Error::Internal`.
+pub fn thread_root<R>(
+    client: &R,
+    token: &str,
+    channel: &str,
+    thread_ts: Timestamp,
+) -> Result<Message, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let request = RepliesRequest {
+        channel,
+        ts: thread_ts,
+        limit: Some(1),
+        ..Default::default()
+    };
+    replies(client, token, &request)?
+        .messages
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            Error::Internal(format!(
+                "conversations.replies returned no messages for {} in {}",
+                thread_ts, channel
+            ))
+        })
+}
+
+/// Counts the messages in `channel` posted after its `last_read` marker.
+///
+/// Reads `last_read` with `conversations.info`, then pages through
+/// `conversations.history` from there. The message at `last_read` has been
+/// read and isn't counted. Without a `last_read`, every message counts.
+pub fn unread_count<R>(client: &R, token: &str, channel: &str) -> Result<u32, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let info = info(
+        client,
+        token,
+        &InfoRequest {
+            channel,
+            ..Default::default()
+        },
+    )?;
+    let oldest = match info.channel.last_read {
+        Some(ref last_read) => Some(Timestamp::parse(last_read).ok_or_else(|| {
+            Error::Internal(format!("invalid last_read {:?} for {}", last_read, channel))
+        })?),
+        None => None,
+    };
+    let mut cursor = None::<String>;
+    let mut count = 0;
+    loop {
+        let response = history(
+            client,
+            token,
+            &HistoryRequest {
+                channel,
+                cursor: cursor.as_deref(),
+                limit: Some(200),
+                oldest,
+                inclusive: Some(false),
+                ..Default::default()
+            },
+        )?;
+        count += response.messages.len() as u32;
+        match response.response_metadata.next_cursor() {
+            Some(next) if response.has_more => cursor = Some(next.to_owned()),
+            _ => return Ok(count),
+        }
+    }
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct OpenRequest<'a> {
+    /// Resume a conversation by its id.
+    pub channel: Option<&'a str>,
+    /// Users to open a conversation with. One user opens an im, several an mpim.
+    pub users: Option<&'a [&'a str]>,
+    /// Return the full im channel definition.
+    pub return_im: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct OpenResponse {
+    pub channel: Conversation,
+    pub no_op: Option<bool>,
+    pub already_open: Option<bool>,
+}
+
+/// Opens or resumes a direct message or multi-person direct message.
+///
+/// Wraps https://api.slack.com/methods/conversations.open
+pub fn open<R>(client: &R, token: &str, request: &OpenRequest<'_>) -> Result<OpenResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let users = request.users.map(|users| users.join(","));
+    let params = vec![
+        Some(("token", token)),
+        request.channel.map(|channel| ("channel", channel)),
+        users.as_ref().map(|users| ("users", &users[..])),
+        request
+            .return_im
+            .map(|return_im| ("return_im", flag(return_im))),
+    ];
+    let params = params.into_iter().flatten().collect::<Vec<_>>();
+    call(client, "conversations.open", &params)
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct CloseRequest<'a> {
+    /// Im or mpim to close.
+    pub channel: &'a str,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CloseResponse {
+    pub no_op: Option<bool>,
+    pub already_closed: Option<bool>,
+}
+
+/// Closes a direct message or multi-person direct message.
+///
+/// Wraps https://api.slack.com/methods/conversations.close
+pub fn close<R>(client: &R, token: &str, request: &CloseRequest<'_>) -> Result<CloseResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let params = [("token", token), ("channel", request.channel)];
+    call(client, "conversations.close", &params)
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct SetTopicRequest<'a> {
+    /// Conversation to set the topic of.
+    pub channel: &'a str,
+    /// The new topic.
+    pub topic: &'a str,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct SetTopicResponse {
+    pub channel: Conversation,
+}
+
+impl SetTopicResponse {
+    /// The topic as it was set
+    pub fn topic(&self) -> Option<&str> {
+        self.channel.topic.as_ref()?.value.as_deref()
+    }
+}
+
+/// Sets the topic for a conversation.
+///
+/// Wraps https://api.slack.com/methods/conversations.setTopic
+pub fn set_topic<R>(
+    client: &R,
+    token: &str,
+    request: &SetTopicRequest<'_>,
+) -> Result<SetTopicResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let params = [
+        ("token", token),
+        ("channel", request.channel),
+        ("topic", request.topic),
+    ];
+    call(client, "conversations.setTopic", &params)
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct SetPurposeRequest<'a> {
+    /// Conversation to set the purpose of.
+    pub channel: &'a str,
+    /// The new purpose.
+    pub purpose: &'a str,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct SetPurposeResponse {
+    pub channel: Conversation,
+}
+
+impl SetPurposeResponse {
+    /// The purpose as it was set
+    pub fn purpose(&self) -> Option<&str> {
+        self.channel.purpose.as_ref()?.value.as_deref()
+    }
+
+    /// The purpose with who set it and when
+    pub fn purpose_details(&self) -> Option<&ChannelPurpose> {
+        self.channel.purpose.as_ref()
+    }
+}
+
+/// Sets the purpose for a conversation.
+///
+/// Wraps https://api.slack.com/methods/conversations.setPurpose
+pub fn set_purpose<R>(
+    client: &R,
+    token: &str,
+    request: &SetPurposeRequest<'_>,
+) -> Result<SetPurposeResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let params = [
+        ("token", token),
+        ("channel", request.channel),
+        ("purpose", request.purpose),
+    ];
+    call(client, "conversations.setPurpose", &params)
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct RenameRequest<'a> {
+    /// Conversation to rename.
+    pub channel: &'a str,
+    /// The new name.
+    pub name: &'a str,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct RenameResponse {
+    pub channel: Conversation,
+}
+
+impl RenameResponse {
+    /// The name the conversation now has
+    pub fn name(&self) -> Option<&str> {
+        self.channel.name.as_deref()
+    }
+}
+
+/// Renames a conversation.
+///
+/// Wraps https://api.slack.com/methods/conversations.rename
+pub fn rename<R>(
+    client: &R,
+    token: &str,
+    request: &RenameRequest<'_>,
+) -> Result<RenameResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let params = [
+        ("token", token),
+        ("channel", request.channel),
+        ("name", request.name),
+    ];
+    call(client, "conversations.rename", &params)
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct ArchiveRequest<'a> {
+    /// Conversation to archive.
+    pub channel: &'a str,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ArchiveResponse {}
+
+/// Archives a conversation.
+///
+/// Wraps https://api.slack.com/methods/conversations.archive
+pub fn archive<R>(
+    client: &R,
+    token: &str,
+    request: &ArchiveRequest<'_>,
+) -> Result<ArchiveResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let params = [("token", token), ("channel", request.channel)];
+    call(client, "conversations.archive", &params)
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct UnarchiveRequest<'a> {
+    /// Conversation to unarchive.
+    pub channel: &'a str,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct UnarchiveResponse {}
+
+/// Reverses conversation archival.
+///
+/// Wraps https://api.slack.com/methods/conversations.unarchive
+pub fn unarchive<R>(
+    client: &R,
+    token: &str,
+    request: &UnarchiveRequest<'_>,
+) -> Result<UnarchiveResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let params = [("token", token), ("channel", request.channel)];
+    call(client, "conversations.unarchive", &params)
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct InviteRequest<'a> {
+    /// Conversation to invite users to.
+    pub channel: &'a str,
+    /// Users to invite, at most 1000.
+    pub users: &'a [&'a str],
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct InviteResponse {
+    pub channel: Conversation,
+}
+
+/// What `invite` did, telling users who were already members apart from
+/// failures
+#[derive(Clone, Debug)]
+pub enum InviteOutcome {
+    /// The users were added, with the channel as it is now
+    Invited(Box<Conversation>),
+    /// The single user invited was a member already. With several users
+    /// Slack rejects the whole call, which is an `Error::Slack` with the code
+    /// `already_in_channel`.
+    AlreadyInChannel,
+}
+
+/// Invites users to a channel.
+///
+/// Wraps https://api.slack.com/methods/conversations.invite
+pub fn invite<R>(
+    client: &R,
+    token: &str,
+    request: &InviteRequest<'_>,
+) -> Result<InviteOutcome, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let users = request.users.join(",");
+    let params = [
+        ("token", token),
+        ("channel", request.channel),
+        ("users", &users[..]),
+    ];
+    // for several users the code doesn't say which, and none were invited
+    let response: Option<InviteResponse> = if request.users.len() == 1 {
+        call_allowing(
+            client,
+            "conversations.invite",
+            &params,
+            "already_in_channel",
+        )?
+    } else {
+        Some(call(client, "conversations.invite", &params)?)
+    };
+    Ok(match response {
+        Some(response) => InviteOutcome::Invited(Box::new(response.channel)),
+        None => InviteOutcome::AlreadyInChannel,
+    })
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct KickRequest<'a> {
+    /// Conversation to remove the user from.
+    pub channel: &'a str,
+    /// User to remove.
+    pub user: &'a str,
+}
+
+/// Slack answers a kick without the updated conversation; use `info` to
+/// fetch it.
+#[derive(Clone, Debug, Deserialize)]
+pub struct KickResponse {}
+
+/// What `kick` did, telling a user who wasn't a member apart from failures
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KickOutcome {
+    Kicked,
+    /// The user wasn't in the conversation
+    NotInChannel,
+}
+
+/// Removes a user from a conversation.
+///
+/// Wraps https://api.slack.com/methods/conversations.kick
+pub fn kick<R>(client: &R, token: &str, request: &KickRequest<'_>) -> Result<KickOutcome, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let params = [
+        ("token", token),
+        ("channel", request.channel),
+        ("user", request.user),
+    ];
+    let response: Option<KickResponse> =
+        call_allowing(client, "conversations.kick", &params, "not_in_channel")?;
+    Ok(match response {
+        Some(_) => KickOutcome::Kicked,
+        None => KickOutcome::NotInChannel,
+    })
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct MarkRequest<'a> {
+    /// Conversation to set the read cursor of.
+    pub channel: &'a str,
+    /// Timestamp of the most recently seen message.
+    pub ts: Timestamp,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MarkResponse {}
+
+/// Sets the read cursor in a conversation.
+///
+/// Wraps https://api.slack.com/methods/conversations.mark
+pub fn mark<R>(client: &R, token: &str, request: &MarkRequest<'_>) -> Result<MarkResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let ts = request.ts.to_param_value();
+    let params = [
+        ("token", token),
+        ("channel", request.channel),
+        ("ts", &ts[..]),
+    ];
+    call(client, "conversations.mark", &params)
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct CreateRequest<'a> {
+    /// Name of the channel to create.
+    pub name: &'a str,
+    /// Create a private channel instead of a public one.
+    pub is_private: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CreateResponse {
+    pub channel: Conversation,
+}
+
+/// Initiates a public or private channel-based conversation.
+///
+/// Wraps https://api.slack.com/methods/conversations.create
+pub fn create<R>(
+    client: &R,
+    token: &str,
+    request: &CreateRequest<'_>,
+) -> Result<CreateResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let params = vec![
+        Some(("token", token)),
+        Some(("name", request.name)),
+        request
+            .is_private
+            .map(|is_private| ("is_private", flag(is_private))),
+    ];
+    let params = params.into_iter().flatten().collect::<Vec<_>>();
+    call(client, "conversations.create", &params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mock::MockSender;
+    use crate::MessageExt;
+
+    #[test]
+    fn info_of_im() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "channel": {
+                "id": "D0C0F7S8Y",
+                "created": 1498500348,
+                "is_im": true,
+                "is_org_shared": false,
+                "user": "U0BS9U4SV",
+                "last_read": "1502126650.228446",
+                "latest": null,
+                "unread_count": 0,
+                "is_open": true
+            }
+        }"#]);
+        let response = info(
+            &client,
+            "xoxb-token",
+            &InfoRequest {
+                channel: "D0C0F7S8Y",
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let channel = response.channel;
+        assert_eq!(channel.id, "D0C0F7S8Y");
+        assert_eq!(channel.is_im, Some(true));
+        assert_eq!(channel.user.unwrap(), "U0BS9U4SV");
+        assert_eq!(channel.last_read.unwrap(), "1502126650.228446");
+        assert_eq!(
+            client.requests()[0].0,
+            "https://slack.com/api/conversations.info"
+        );
+    }
+
+    #[test]
+    fn info_of_shared_channel() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "channel": {
+                "id": "C012AB3CD",
+                "name": "acme-partners",
+                "is_channel": true,
+                "is_private": false,
+                "is_shared": true,
+                "is_ext_shared": true,
+                "is_org_shared": false,
+                "is_pending_ext_shared": false,
+                "shared_team_ids": ["T12345678", "T87654321"],
+                "pending_shared": [],
+                "context_team_id": "T12345678",
+                "conversation_host_id": "T12345678"
+            }
+        }"#]);
+        let channel = info(
+            &client,
+            "xoxb-token",
+            &InfoRequest {
+                channel: "C012AB3CD",
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .channel;
+        assert_eq!(channel.is_ext_shared, Some(true));
+        assert_eq!(channel.is_org_shared, Some(false));
+        assert_eq!(
+            channel.shared_team_ids.unwrap(),
+            vec!["T12345678", "T87654321"]
+        );
+    }
+
+    #[test]
+    fn members_all_follows_cursor() {
+        let client = MockSender::new(&[
+            r#"{
+                "ok": true,
+                "members": ["U023BECGF", "U061F7AUR"],
+                "response_metadata": { "next_cursor": "e3VzZXJfaWQ6IFcxMjM0NTY3fQ==" }
+            }"#,
+            r#"{
+                "ok": true,
+                "members": ["W012A3CDE"],
+                "response_metadata": { "next_cursor": "" }
+            }"#,
+        ]);
+        let members = members_all(&client, "xoxb-token", "C012AB3CD", Some(2)).unwrap();
+        assert_eq!(members, vec!["U023BECGF", "U061F7AUR", "W012A3CDE"]);
+        assert_eq!(client.requests().len(), 2);
+        assert!(client.param(0, "cursor").is_none());
+        assert_eq!(
+            client.param(1, "cursor").unwrap(),
+            "e3VzZXJfaWQ6IFcxMjM0NTY3fQ=="
+        );
+        assert_eq!(client.param(1, "limit").unwrap(), "2");
+    }
+
+    #[test]
+    fn history_page() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "messages": [
+                {
+                    "type": "message",
+                    "user": "U012AB3CDE",
+                    "text": "I find you punny and would like to smell your nose letter",
+                    "ts": "1512085950.000216"
+                },
+                {
+                    "type": "message",
+                    "subtype": "channel_join",
+                    "user": "U061F7AUR",
+                    "text": "<@U061F7AUR> has joined the channel",
+                    "ts": "1512104434.000490"
+                }
+            ],
+            "has_more": true,
+            "pin_count": 0,
+            "response_metadata": { "next_cursor": "bmV4dF90czoxNTEyMDg1ODYxMDAwNTQz" }
+        }"#]);
+        let response = history(
+            &client,
+            "xoxb-token",
+            &HistoryRequest {
+                channel: "CBR2V3XEX",
+                limit: Some(2),
+                oldest: Some(Timestamp::from(1512085950)),
+                inclusive: Some(true),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(response.messages.len(), 2);
+        assert_eq!(
+            response.messages[0].text().unwrap(),
+            "I find you punny and would like to smell your nose letter"
+        );
+        assert!(response.has_more);
+        assert_eq!(
+            response.response_metadata.next_cursor().unwrap(),
+            "bmV4dF90czoxNTEyMDg1ODYxMDAwNTQz"
+        );
+        assert_eq!(
+            client.requests()[0].0,
+            "https://slack.com/api/conversations.history"
+        );
+        assert_eq!(client.param(0, "limit").unwrap(), "2");
+        assert_eq!(client.param(0, "oldest").unwrap(), "1512085950.000000");
+        assert_eq!(client.param(0, "inclusive").unwrap(), "1");
+        assert!(client.param(0, "cursor").is_none());
+    }
+
+    #[test]
+    fn thread_root_is_first_reply() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "messages": [
+                {
+                    "type": "message",
+                    "user": "U061F7AUR",
+                    "text": "island",
+                    "thread_ts": "1482960137.003543",
+                    "reply_count": 3,
+                    "ts": "1482960137.003543"
+                }
+            ],
+            "has_more": true,
+            "response_metadata": {"next_cursor": "bmV4dF90czoxNDg0Njc4MjkwNTE3MDkx"}
+        }"#]);
+        let ts = Timestamp::parse("1482960137.003543").unwrap();
+        let root = thread_root(&client, "xoxb-token", "C123ABC456", ts).unwrap();
+        assert_eq!(root.text(), Some("island"));
+        assert_eq!(root.ts(), Some(ts));
+        assert_eq!(
+            client.requests()[0].0,
+            "https://slack.com/api/conversations.replies"
+        );
+        assert_eq!(client.param(0, "channel").unwrap(), "C123ABC456");
+        assert_eq!(client.param(0, "ts").unwrap(), "1482960137.003543");
+        assert_eq!(client.param(0, "limit").unwrap(), "1");
+    }
+
+    #[test]
+    fn thread_root_of_deleted_thread() {
+        let client = MockSender::new(&[
+            r#"{"ok": false, "error": "thread_not_found"}"#,
+            r#"{"ok": true, "messages": []}"#,
+        ]);
+        let ts = Timestamp::parse("1482960137.003543").unwrap();
+        let err = thread_root(&client, "xoxb-token", "C123ABC456", ts).unwrap_err();
+        assert_eq!(err.code(), Some("thread_not_found"));
+        match thread_root(&client, "xoxb-token", "C123ABC456", ts).unwrap_err() {
+            Error::Internal(msg) => assert!(msg.contains("no messages"), "{}", msg),
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn unread_count_pages_after_last_read() {
+        let client = MockSender::new(&[
+            r#"{"ok": true, "channel": {"id": "C1", "last_read": "1512085950.000216"}}"#,
+            r#"{"ok": true,
+                "messages": [{"type": "message", "ts": "1512085952.000001"},
+                             {"type": "message", "ts": "1512085951.000001"}],
+                "has_more": true,
+                "response_metadata": {"next_cursor": "bmV4dA=="}}"#,
+            r#"{"ok": true,
+                "messages": [{"type": "message", "ts": "1512085950.000217"}],
+                "has_more": false,
+                "response_metadata": {"next_cursor": ""}}"#,
+        ]);
+        assert_eq!(unread_count(&client, "xoxb-token", "C1").unwrap(), 3);
+        assert_eq!(
+            client.requests()[0].0,
+            "https://slack.com/api/conversations.info"
+        );
+        assert_eq!(client.param(1, "oldest").unwrap(), "1512085950.000216");
+        assert_eq!(client.param(1, "inclusive").unwrap(), "0");
+        assert!(client.param(1, "cursor").is_none());
+        assert_eq!(client.param(2, "cursor").unwrap(), "bmV4dA==");
+        assert_eq!(client.param(2, "oldest").unwrap(), "1512085950.000216");
+    }
+
+    #[test]
+    fn unread_count_without_last_read() {
+        let client = MockSender::new(&[
+            r#"{"ok": true, "channel": {"id": "D1", "is_im": true}}"#,
+            r#"{"ok": true, "messages": [{"type": "message", "ts": "1.000001"}]}"#,
+        ]);
+        assert_eq!(unread_count(&client, "xoxb-token", "D1").unwrap(), 1);
+        assert!(client.param(1, "oldest").is_none());
+    }
+
+    #[test]
+    fn unread_count_error() {
+        let client = MockSender::new(&[r#"{"ok": false, "error": "channel_not_found"}"#]);
+        let err = unread_count(&client, "xoxb-token", "C1").unwrap_err();
+        assert_eq!(err.code(), Some("channel_not_found"));
+    }
+
+    #[test]
+    fn open_mpim() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "channel": { "id": "G4BCBJ3JX" }
+        }"#]);
+        let response = open(
+            &client,
+            "xoxb-token",
+            &OpenRequest {
+                users: Some(&["W1234567890", "U2345678901", "U3456789012"]),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(response.channel.id, "G4BCBJ3JX");
+        assert_eq!(
+            client.param(0, "users").unwrap(),
+            "W1234567890,U2345678901,U3456789012"
+        );
+        assert!(client.param(0, "channel").is_none());
+    }
+
+    #[test]
+    fn reopen_channel() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "no_op": true,
+            "already_open": true,
+            "channel": { "id": "D069C7QFK" }
+        }"#]);
+        let response = open(
+            &client,
+            "xoxb-token",
+            &OpenRequest {
+                channel: Some("D069C7QFK"),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(response.channel.id, "D069C7QFK");
+        assert_eq!(response.already_open, Some(true));
+        assert_eq!(client.param(0, "channel").unwrap(), "D069C7QFK");
+    }
+
+    #[test]
+    fn set_topic_of_private_channel() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "channel": {
+                "id": "G1234567890",
+                "is_private": true,
+                "topic": {
+                    "value": "Apply topically for best effects",
+                    "creator": "U0BPQUNTA",
+                    "last_set": 1516927011
+                }
+            }
+        }"#]);
+        let response = set_topic(
+            &client,
+            "xoxb-token",
+            &SetTopicRequest {
+                channel: "G1234567890",
+                topic: "Apply topically for best effects",
+            },
+        )
+        .unwrap();
+        assert_eq!(response.topic(), Some("Apply topically for best effects"));
+        assert_eq!(
+            client.requests()[0].0,
+            "https://slack.com/api/conversations.setTopic"
+        );
+        assert_eq!(
+            client.param(0, "topic").unwrap(),
+            "Apply topically for best effects"
+        );
+    }
+
+    #[test]
+    fn set_purpose_of_channel() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "channel": {
+                "id": "C1234567890",
+                "purpose": { "value": "Ideas", "creator": "U0BPQUNTA", "last_set": 1516927011 }
+            }
+        }"#]);
+        let response = set_purpose(
+            &client,
+            "xoxb-token",
+            &SetPurposeRequest {
+                channel: "C1234567890",
+                purpose: "Ideas",
+            },
+        )
+        .unwrap();
+        assert_eq!(response.purpose(), Some("Ideas"));
+        assert_eq!(client.param(0, "purpose").unwrap(), "Ideas");
+    }
+
+    #[test]
+    fn set_purpose_details() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "channel": {
+                "id": "C1234567890",
+                "purpose": {
+                    "value": "I didn't set this purpose on purpose!",
+                    "creator": "W1234567890",
+                    "last_set": 1516927011
+                }
+            }
+        }"#]);
+        let response = set_purpose(
+            &client,
+            "xoxb-token",
+            &SetPurposeRequest {
+                channel: "C1234567890",
+                purpose: "I didn't set this purpose on purpose!",
+            },
+        )
+        .unwrap();
+        let purpose = response.purpose_details().unwrap();
+        assert_eq!(purpose.creator.as_ref().unwrap(), "W1234567890");
+        assert_eq!(purpose.last_set, Some(1516927011));
+    }
+
+    #[test]
+    fn rename_returns_full_conversation() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "channel": {
+                "id": "C0EAQDV4Z",
+                "name": "endeavor",
+                "is_channel": true,
+                "is_private": false,
+                "is_archived": false,
+                "is_member": true,
+                "created": 1504554479,
+                "creator": "U0123456",
+                "last_read": "1504554479.000002",
+                "num_members": 3,
+                "topic": {"value": "", "creator": "", "last_set": 0},
+                "purpose": {"value": "Trips", "creator": "U0123456", "last_set": 1504554479},
+                "previous_names": ["voyager"]
+            }
+        }"#]);
+        let response = rename(
+            &client,
+            "xoxb-token",
+            &RenameRequest {
+                channel: "C0EAQDV4Z",
+                name: "endeavor",
+            },
+        )
+        .unwrap();
+        assert_eq!(response.name(), Some("endeavor"));
+        let channel = response.channel;
+        assert_eq!(channel.id, "C0EAQDV4Z");
+        assert_eq!(channel.is_member, Some(true));
+        assert_eq!(channel.creator.as_deref(), Some("U0123456"));
+        assert_eq!(channel.num_members, Some(3));
+        assert_eq!(channel.last_read.as_deref(), Some("1504554479.000002"));
+        assert_eq!(channel.purpose.unwrap().value.as_deref(), Some("Trips"));
+        assert_eq!(client.param(0, "channel").unwrap(), "C0EAQDV4Z");
+        assert_eq!(client.param(0, "name").unwrap(), "endeavor");
+    }
+
+    #[test]
+    fn rename_error() {
+        let client = MockSender::new(&[r#"{"ok": false, "error": "name_taken"}"#]);
+        let err = rename(
+            &client,
+            "xoxb-token",
+            &RenameRequest {
+                channel: "C1234567890",
+                name: "general",
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), Some("name_taken"));
+    }
+
+    #[test]
+    fn archive_and_unarchive() {
+        let client = MockSender::new(&[r#"{"ok": true}"#, r#"{"ok": true}"#]);
+        archive(
+            &client,
+            "xoxb-token",
+            &ArchiveRequest {
+                channel: "G1234567890",
+            },
+        )
+        .unwrap();
+        unarchive(
+            &client,
+            "xoxb-token",
+            &UnarchiveRequest {
+                channel: "G1234567890",
+            },
+        )
+        .unwrap();
+        let requests = client.requests();
+        assert_eq!(requests[0].0, "https://slack.com/api/conversations.archive");
+        assert_eq!(
+            requests[1].0,
+            "https://slack.com/api/conversations.unarchive"
+        );
+        assert_eq!(client.param(1, "channel").unwrap(), "G1234567890");
+    }
+
+    #[test]
+    fn archive_error() {
+        let client = MockSender::new(&[r#"{"ok": false, "error": "already_archived"}"#]);
+        let err = archive(
+            &client,
+            "xoxb-token",
+            &ArchiveRequest {
+                channel: "C1234567890",
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), Some("already_archived"));
+    }
+
+    #[test]
+    fn invite_several_users() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "channel": { "id": "C012AB3CD", "name": "general", "num_members": 4 }
+        }"#]);
+        let response = invite(
+            &client,
+            "xoxb-token",
+            &InviteRequest {
+                channel: "C012AB3CD",
+                users: &["W1234567890", "U2345678901"],
+            },
+        )
+        .unwrap();
+        match response {
+            InviteOutcome::Invited(channel) => assert_eq!(channel.num_members, Some(4)),
+            outcome => panic!("unexpected outcome: {:?}", outcome),
+        }
+        assert_eq!(client.param(0, "users").unwrap(), "W1234567890,U2345678901");
+    }
+
+    #[test]
+    fn invite_outcomes() {
+        let client = MockSender::new(&[
+            r#"{"ok": false, "error": "already_in_channel"}"#,
+            r#"{"ok": false, "error": "user_not_found"}"#,
+        ]);
+        let request = InviteRequest {
+            channel: "C012AB3CD",
+            users: &["W1234567890"],
+        };
+        match invite(&client, "xoxb-token", &request).unwrap() {
+            InviteOutcome::AlreadyInChannel => {}
+            outcome => panic!("unexpected outcome: {:?}", outcome),
+        }
+        let err = invite(&client, "xoxb-token", &request).unwrap_err();
+        assert_eq!(err.code(), Some("user_not_found"));
+    }
+
+    #[test]
+    fn invite_several_with_a_member_is_error() {
+        let client = MockSender::new(&[r#"{"ok": false, "error": "already_in_channel"}"#]);
+        let request = InviteRequest {
+            channel: "C012AB3CD",
+            users: &["W1234567890", "U2345678901"],
+        };
+        let err = invite(&client, "xoxb-token", &request).unwrap_err();
+        assert_eq!(err.code(), Some("already_in_channel"));
+        assert_eq!(client.param(0, "users").unwrap(), "W1234567890,U2345678901");
+    }
+
+    #[test]
+    fn close_already_closed() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "no_op": true,
+            "already_closed": true
+        }"#]);
+        let response = close(
+            &client,
+            "xoxb-token",
+            &CloseRequest {
+                channel: "D1234567890",
+            },
+        )
+        .unwrap();
+        assert_eq!(response.already_closed, Some(true));
+        assert_eq!(
+            client.requests()[0].0,
+            "https://slack.com/api/conversations.close"
+        );
+        assert_eq!(client.param(0, "channel").unwrap(), "D1234567890");
+    }
+
+    #[test]
+    fn kick_user() {
+        let client = MockSender::new(&[r#"{"ok": true}"#]);
+        let outcome = kick(
+            &client,
+            "xoxb-token",
+            &KickRequest {
+                channel: "C012AB3CD",
+                user: "W1234567890",
+            },
+        )
+        .unwrap();
+        assert_eq!(outcome, KickOutcome::Kicked);
+        assert_eq!(
+            client.requests()[0].0,
+            "https://slack.com/api/conversations.kick"
+        );
+        assert_eq!(client.param(0, "user").unwrap(), "W1234567890");
+    }
+
+    #[test]
+    fn kick_outcomes() {
+        let client = MockSender::new(&[
+            r#"{"ok": false, "error": "not_in_channel"}"#,
+            r#"{"ok": false, "error": "cant_kick_self"}"#,
+        ]);
+        let request = KickRequest {
+            channel: "C012AB3CD",
+            user: "W1234567890",
+        };
+        assert_eq!(
+            kick(&client, "xoxb-token", &request).unwrap(),
+            KickOutcome::NotInChannel
+        );
+        let err = kick(&client, "xoxb-token", &request).unwrap_err();
+        assert_eq!(err.code(), Some("cant_kick_self"));
+    }
+
+    #[test]
+    fn mark_read_cursor() {
+        let client = MockSender::new(&[r#"{"ok": true}"#]);
+        mark(
+            &client,
+            "xoxb-token",
+            &MarkRequest {
+                channel: "D0C0F7S8Y",
+                ts: Timestamp::from((1593473566, 0.000200)),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            client.requests()[0].0,
+            "https://slack.com/api/conversations.mark"
+        );
+        assert_eq!(client.param(0, "channel").unwrap(), "D0C0F7S8Y");
+        assert_eq!(client.param(0, "ts").unwrap(), "1593473566.000200");
+    }
+
+    #[test]
+    fn create_public_and_private() {
+        let client = MockSender::new(&[
+            r#"{"ok": true, "channel": {"id": "C0EAQDV4Z", "name": "endeavor", "is_channel": true, "is_private": false}}"#,
+            r#"{"ok": true, "channel": {"id": "C0EAQDV50", "name": "secret", "is_channel": true, "is_private": true}}"#,
+        ]);
+        let public = create(
+            &client,
+            "xoxb-token",
+            &CreateRequest {
+                name: "endeavor",
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let private = create(
+            &client,
+            "xoxb-token",
+            &CreateRequest {
+                name: "secret",
+                is_private: Some(true),
+            },
+        )
+        .unwrap();
+        assert_eq!(public.channel.is_private, Some(false));
+        assert_eq!(private.channel.is_private, Some(true));
+        assert!(client.param(0, "is_private").is_none());
+        assert_eq!(client.param(1, "is_private").unwrap(), "1");
+        assert_eq!(client.param(1, "name").unwrap(), "secret");
+    }
+}
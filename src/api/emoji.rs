@@ -0,0 +1,130 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub use slack_api::sync::emoji::*;
+
+use std::collections::HashMap;
+
+use crate::api::call;
+use crate::api::requests::SlackWebRequestSender;
+use crate::error::Error;
+
+/// A custom emoji, either an image or another name for an emoji
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(from = "String")]
+pub enum Emoji {
+    /// Url of the emoji's image
+    Url(String),
+    /// Name of the emoji this one stands for, without the `alias:` prefix
+    Alias(String),
+}
+
+impl From<String> for Emoji {
+    fn from(value: String) -> Emoji {
+        match value.strip_prefix("alias:") {
+            Some(name) => Emoji::Alias(name.to_owned()),
+            None => Emoji::Url(value),
+        }
+    }
+}
+
+/// Shadows `slack_api`'s version, which expects booleans instead of urls.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ListResponse {
+    /// Custom emoji by name
+    #[serde(default)]
+    pub emoji: HashMap<String, Emoji>,
+}
+
+impl ListResponse {
+    /// The image url of the custom emoji `name`, following aliases.
+    ///
+    /// `None` if there is no such custom emoji, including aliases of
+    /// standard emoji, or the aliases go round in a circle.
+    pub fn resolve(&self, name: &str) -> Option<&str> {
+        let mut name = name.trim_matches(':');
+        // a chain longer than the list must revisit a name
+        for _ in 0..=self.emoji.len() {
+            match *self.emoji.get(name)? {
+                Emoji::Url(ref url) => return Some(url),
+                Emoji::Alias(ref target) => name = target,
+            }
+        }
+        None
+    }
+}
+
+/// Lists the team's custom emoji.
+///
+/// Wraps https://api.slack.com/methods/emoji.list
+pub fn list<R>(client: &R, token: &str) -> Result<ListResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    call(client, "emoji.list", &[("token", token)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mock::MockSender;
+
+    fn emoji() -> ListResponse {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "emoji": {
+                "bowtie": "https://my.slack.com/emoji/bowtie/46ec6f2bb0.png",
+                "squirrel": "https://my.slack.com/emoji/squirrel/f35f40c0e0.png",
+                "shipit": "alias:squirrel",
+                "ship-it": "alias:shipit",
+                "like": "alias:thumbsup",
+                "ping": "alias:pong",
+                "pong": "alias:ping"
+            }
+        }"#]);
+        let response = list(&client, "xoxb-token").unwrap();
+        assert_eq!(client.requests()[0].0, "https://slack.com/api/emoji.list");
+        response
+    }
+
+    #[test]
+    fn list_decodes_urls_and_aliases() {
+        let response = emoji();
+        assert_eq!(
+            response.emoji["bowtie"],
+            Emoji::Url("https://my.slack.com/emoji/bowtie/46ec6f2bb0.png".into())
+        );
+        assert_eq!(response.emoji["shipit"], Emoji::Alias("squirrel".into()));
+    }
+
+    #[test]
+    fn resolve_follows_alias_chain() {
+        let response = emoji();
+        let squirrel = Some("https://my.slack.com/emoji/squirrel/f35f40c0e0.png");
+        assert_eq!(response.resolve("squirrel"), squirrel);
+        assert_eq!(response.resolve("shipit"), squirrel);
+        assert_eq!(response.resolve(":ship-it:"), squirrel);
+    }
+
+    #[test]
+    fn resolve_unknown_and_circular() {
+        let response = emoji();
+        assert_eq!(response.resolve("nope"), None);
+        // aliases of standard emoji have no custom image
+        assert_eq!(response.resolve("like"), None);
+        assert_eq!(response.resolve("ping"), None);
+    }
+}
@@ -0,0 +1,212 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+pub use slack_api::sync::files::*;
+
+use crate::api::requests::SlackWebRequestSender;
+use crate::api::{call, File, FileComment, HttpClient};
+use crate::error::Error;
+
+/// Downloads the contents of a file.
+///
+/// Fetches `url_private_download` (or `url_private` if absent) with the token
+/// as a bearer token, as Slack requires for private file urls.
+pub fn download(client: &HttpClient, token: &str, file: &File) -> Result<Vec<u8>, Error> {
+    let url = file
+        .url_private_download
+        .as_ref()
+        .or(file.url_private.as_ref())
+        .ok_or_else(|| {
+            Error::Internal(format!(
+                "File {} has no private url",
+                file.id.as_deref().unwrap_or("<unknown>")
+            ))
+        })?;
+    let response = client
+        .get(url.as_str())
+        .bearer_auth(token)
+        .send()?
+        .error_for_status()?;
+    Ok(response.bytes()?.to_vec())
+}
+
+/// Lists all files matching `request`, following the paging across pages.
+///
+/// Starts at `request.page`, or the first page, and uses `request.count` as
+/// the page size. A `pages` of `0`, as Slack reports when nothing matches,
+/// ends after the first page.
+pub fn list_all<R>(client: &R, token: &str, request: &ListRequest<'_>) -> Result<Vec<File>, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let mut request = request.clone();
+    let mut page = request.page.unwrap_or(1).max(1);
+    let mut all = Vec::new();
+    loop {
+        request.page = Some(page);
+        let response = list(client, token, &request)?;
+        all.extend(response.files.unwrap_or_default());
+        let pages = response.paging.and_then(|p| p.pages).unwrap_or(0);
+        if i64::from(page) >= i64::from(pages) {
+            return Ok(all);
+        }
+        page += 1;
+    }
+}
+
+#[derive(Deserialize)]
+struct CommentsAddResponse {
+    comment: FileComment,
+}
+
+/// Adds a comment to a file and returns the new comment.
+///
+/// Wraps https://api.slack.com/methods/files.comments.add
+pub fn comments_add<R>(
+    client: &R,
+    token: &str,
+    file: &str,
+    comment: &str,
+) -> Result<FileComment, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let params = [("token", token), ("file", file), ("comment", comment)];
+    let response: CommentsAddResponse = call(client, "files.comments.add", &params)?;
+    Ok(response.comment)
+}
+
+#[derive(Deserialize)]
+struct CommentsDeleteResponse {}
+
+/// Deletes the comment `id` from a file.
+///
+/// Wraps https://api.slack.com/methods/files.comments.delete
+pub fn comments_delete<R>(client: &R, token: &str, file: &str, id: &str) -> Result<(), Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let params = [("token", token), ("file", file), ("id", id)];
+    call::<_, CommentsDeleteResponse>(client, "files.comments.delete", &params)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mock::MockSender;
+
+    fn page(id: &str, page: u32, pages: u32) -> String {
+        format!(
+            r#"{{"ok": true,
+                "files": [{{"id": "{}", "name": "{}.txt", "created": 1531763342, "timestamp": 1531763342}}],
+                "paging": {{"count": 1, "total": {}, "page": {}, "pages": {}}}
+            }}"#,
+            id, id, pages, page, pages
+        )
+    }
+
+    #[test]
+    fn list_all_follows_paging() {
+        let (first, second) = (page("F0S43PZDF", 1, 2), page("F0S43P1CZ", 2, 2));
+        let client = MockSender::new(&[&first, &second]);
+        let request = ListRequest {
+            channel: Some("C1234567890"),
+            count: Some(1),
+            ..Default::default()
+        };
+        let files = list_all(&client, "xoxb", &request).unwrap();
+        let ids = files.iter().map(|f| f.id.as_deref()).collect::<Vec<_>>();
+        assert_eq!(ids, vec![Some("F0S43PZDF"), Some("F0S43P1CZ")]);
+        assert_eq!(client.requests()[0].0, "https://slack.com/api/files.list");
+        assert_eq!(client.param(0, "page").as_deref(), Some("1"));
+        assert_eq!(client.param(1, "page").as_deref(), Some("2"));
+        assert_eq!(client.param(1, "channel").as_deref(), Some("C1234567890"));
+    }
+
+    #[test]
+    fn list_all_with_zero_pages() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "files": [],
+            "paging": {"count": 100, "total": 0, "page": 1, "pages": 0}
+        }"#]);
+        let files = list_all(&client, "xoxb", &Default::default()).unwrap();
+        assert!(files.is_empty());
+        assert_eq!(client.requests().len(), 1);
+    }
+
+    #[test]
+    fn download_without_private_url() {
+        let file: File = serde_json::from_str(r#"{"id": "F0S43PZDF"}"#).unwrap();
+        match download(&HttpClient::new(), "xoxb-token", &file).unwrap_err() {
+            Error::Internal(msg) => assert_eq!(msg, "File F0S43PZDF has no private url"),
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn comments_add_returns_comment() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "comment": {
+                "id": "Fc1234567890",
+                "timestamp": 1356032811,
+                "user": "U1234567890",
+                "comment": "Looks good, but check the error handling"
+            }
+        }"#]);
+        let comment = comments_add(
+            &client,
+            "xoxb",
+            "F1234567890",
+            "Looks good, but check the error handling",
+        )
+        .unwrap();
+        assert_eq!(comment.id.as_deref(), Some("Fc1234567890"));
+        assert_eq!(comment.user.as_deref(), Some("U1234567890"));
+        assert_eq!(
+            client.requests()[0].0,
+            "https://slack.com/api/files.comments.add"
+        );
+        assert_eq!(client.param(0, "file").unwrap(), "F1234567890");
+        assert_eq!(
+            client.param(0, "comment").unwrap(),
+            "Looks good, but check the error handling"
+        );
+    }
+
+    #[test]
+    fn comments_delete_sends_ids() {
+        let client = MockSender::new(&[
+            r#"{"ok": true}"#,
+            r#"{"ok": false, "error": "cant_delete"}"#,
+        ]);
+        comments_delete(&client, "xoxb", "F1234567890", "Fc1234567890").unwrap();
+        assert_eq!(
+            client.requests()[0].0,
+            "https://slack.com/api/files.comments.delete"
+        );
+        assert_eq!(client.param(0, "file").unwrap(), "F1234567890");
+        assert_eq!(client.param(0, "id").unwrap(), "Fc1234567890");
+
+        let err = comments_delete(&client, "xoxb", "F1234567890", "Fc1234567890").unwrap_err();
+        assert_eq!(err.code(), Some("cant_delete"));
+    }
+}
@@ -0,0 +1,116 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::api::{BotIcons, MessageBotMessageIcons};
+
+/// The avatar of a bot or integration, in whichever sizes Slack provides.
+///
+/// Decodes any `icons` object, ignoring keys it doesn't know, and converts
+/// from the `icons` of `Bot` and `MessageBotMessage`.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct Icons {
+    pub image_24: Option<String>,
+    pub image_32: Option<String>,
+    pub image_36: Option<String>,
+    pub image_48: Option<String>,
+    pub image_64: Option<String>,
+    pub image_72: Option<String>,
+    /// Emoji used instead of an image, e.g. `:robot_face:`
+    pub emoji: Option<String>,
+}
+
+impl Icons {
+    /// The largest image available
+    pub fn largest(&self) -> Option<&str> {
+        [
+            &self.image_72,
+            &self.image_64,
+            &self.image_48,
+            &self.image_36,
+            &self.image_32,
+            &self.image_24,
+        ]
+        .iter()
+        .find_map(|image| image.as_deref())
+    }
+}
+
+impl From<BotIcons> for Icons {
+    fn from(icons: BotIcons) -> Icons {
+        Icons {
+            image_36: icons.image_36,
+            image_48: icons.image_48,
+            image_72: icons.image_72,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<MessageBotMessageIcons> for Icons {
+    fn from(icons: MessageBotMessageIcons) -> Icons {
+        Icons {
+            image_36: icons.image_36,
+            image_48: icons.image_48,
+            image_64: icons.image_64,
+            image_72: icons.image_72,
+            emoji: icons.emoji,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{Message, MessageBotMessage};
+
+    #[test]
+    fn decode_ignores_unknown_sizes() {
+        let icons: Icons = serde_json::from_str(
+            r#"{
+                "image_24": "https://example.com/24.png",
+                "image_48": "https://example.com/48.png",
+                "image_1024": "https://example.com/1024.png"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            icons.image_24.as_ref().unwrap(),
+            "https://example.com/24.png"
+        );
+        assert_eq!(icons.largest(), Some("https://example.com/48.png"));
+    }
+
+    #[test]
+    fn from_bot_message() {
+        let msg: Message = serde_json::from_str(
+            r#"{
+                "type": "message",
+                "subtype": "bot_message",
+                "bot_id": "B0AB12CDE",
+                "text": "beep",
+                "ts": "1358877455.000010",
+                "icons": {"emoji": ":robot_face:", "image_64": "https://example.com/64.png"}
+            }"#,
+        )
+        .unwrap();
+        let icons = match msg {
+            Message::BotMessage(MessageBotMessage { icons, .. }) => Icons::from(icons.unwrap()),
+            _ => panic!("Message decoded into incorrect variant."),
+        };
+        assert_eq!(icons.emoji.as_ref().unwrap(), ":robot_face:");
+        assert_eq!(icons.largest(), Some("https://example.com/64.png"));
+    }
+}
@@ -0,0 +1,126 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::api::{File, FileComment, Message, Timestamp};
+
+/// The target of a reaction or pin.
+///
+/// Events only reference the item by id, while list methods such as
+/// `pins.list` embed the full message, file or comment, so those fields are
+/// optional.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Item {
+    Message {
+        channel: String,
+        ts: Option<Timestamp>,
+        message: Option<Box<Message>>,
+    },
+    File {
+        file: ItemFile,
+    },
+    FileComment {
+        file: ItemFile,
+        /// Id of the comment
+        file_comment: Option<String>,
+        comment: Option<Box<FileComment>>,
+    },
+}
+
+/// A file referenced by an `Item`, either by id or in full
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum ItemFile {
+    Id(String),
+    File(Box<File>),
+}
+
+impl ItemFile {
+    /// The id of the file
+    pub fn id(&self) -> Option<&str> {
+        match *self {
+            ItemFile::Id(ref id) => Some(id),
+            ItemFile::File(ref file) => file.id.as_deref(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MessageExt;
+
+    #[test]
+    fn message_reference() {
+        let item: Item = serde_json::from_str(
+            r#"{"type": "message", "channel": "C0G9QF9GZ", "ts": "1360782400.498405"}"#,
+        )
+        .unwrap();
+        match item {
+            Item::Message {
+                channel,
+                ts,
+                message,
+            } => {
+                assert_eq!(channel, "C0G9QF9GZ");
+                assert_eq!(ts.unwrap().to_param_value(), "1360782400.498405");
+                assert!(message.is_none());
+            }
+            item => panic!("unexpected item: {:?}", item),
+        }
+    }
+
+    #[test]
+    fn embedded_message() {
+        let item: Item = serde_json::from_str(
+            r#"{
+                "type": "message",
+                "channel": "C2U86NC6H",
+                "message": {
+                    "type": "message",
+                    "user": "U2U85N1RV",
+                    "text": "hello",
+                    "ts": "1478730131.000004"
+                }
+            }"#,
+        )
+        .unwrap();
+        match item {
+            Item::Message { message, .. } => {
+                assert_eq!(message.unwrap().text(), Some("hello"));
+            }
+            item => panic!("unexpected item: {:?}", item),
+        }
+    }
+
+    #[test]
+    fn file_by_id_or_in_full() {
+        let item: Item = serde_json::from_str(r#"{"type": "file", "file": "F0HS27V1Z"}"#).unwrap();
+        match item {
+            Item::File { file } => assert_eq!(file.id(), Some("F0HS27V1Z")),
+            item => panic!("unexpected item: {:?}", item),
+        }
+        let item: Item = serde_json::from_str(
+            r#"{"type": "file", "file": {"id": "F12345678", "name": "notes.txt"}}"#,
+        )
+        .unwrap();
+        match item {
+            Item::File {
+                file: ItemFile::File(file),
+            } => assert_eq!(file.name.as_deref(), Some("notes.txt")),
+            item => panic!("unexpected item: {:?}", item),
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Slack Web API, re-exported from `slack_api::sync`.
+//!
+//! Modules declared here extend their `slack_api` counterparts with helpers
+//! that are not (yet) part of the generated API.
+
+pub use slack_api::sync::*;
+
+pub mod auth;
+pub mod chat;
+pub mod conversations;
+pub mod emoji;
+pub mod files;
+mod icons;
+mod item;
+pub mod oauth;
+pub mod pins;
+pub mod reactions;
+pub mod search;
+pub mod team;
+pub mod users;
+
+pub use self::icons::Icons;
+pub use self::item::{Item, ItemFile};
+
+use crate::error::Error;
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+
+/// The blocking HTTP client used for Web API calls
+pub use reqwest::blocking::Client as HttpClient;
+
+/// Settings for the HTTP client built by `client_with_config`
+#[derive(Clone, Debug, Default)]
+pub struct ClientConfig {
+    /// `User-Agent` header sent with every request, identifying your app to Slack
+    pub user_agent: Option<String>,
+    /// Limit on a whole request, from connecting to reading the response body.
+    /// Defaults to `DEFAULT_TIMEOUT`.
+    pub timeout: Option<Duration>,
+    /// Limit on establishing the connection. By default only `timeout` applies.
+    pub connect_timeout: Option<Duration>,
+    /// Upper bound on idle keep-alive connections kept per host.
+    /// By default reqwest keeps any number of them.
+    pub pool_max_idle: Option<usize>,
+    /// How long an idle connection is kept before it is closed.
+    /// Defaults to reqwest's 90 seconds.
+    pub pool_idle_timeout: Option<Duration>,
+}
+
+/// The request timeout used unless `ClientConfig::timeout` is set
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Builds an HTTP client for the Web API from `config`.
+///
+/// The result can be passed to the API methods and to `RtmClient::login_with_client`.
+/// Build it once and share it: its connection pool keeps connections to
+/// Slack alive between calls.
+pub fn client_with_config(config: &ClientConfig) -> Result<HttpClient, Error> {
+    let mut builder = HttpClient::builder().timeout(config.timeout.unwrap_or(DEFAULT_TIMEOUT));
+    if let Some(connect_timeout) = config.connect_timeout {
+        builder = builder.connect_timeout(connect_timeout);
+    }
+    if let Some(max) = config.pool_max_idle {
+        builder = builder.pool_max_idle_per_host(max);
+    }
+    if let Some(idle_timeout) = config.pool_idle_timeout {
+        builder = builder.pool_idle_timeout(idle_timeout);
+    }
+    if let Some(ref user_agent) = config.user_agent {
+        builder = builder.user_agent(user_agent.as_str());
+    }
+    Ok(builder.build()?)
+}
+
+/// The `ok`/`error` envelope every Web API response carries
+#[derive(Deserialize)]
+pub(crate) struct Status {
+    #[serde(default)]
+    pub(crate) ok: bool,
+    pub(crate) error: Option<String>,
+}
+
+/// Calls the Web API `method` and decodes the response into `T`.
+///
+/// A response with `"ok": false` is returned as `Error::Slack`.
+pub(crate) fn call<R, T>(client: &R, method: &str, params: &[(&str, &str)]) -> Result<T, Error>
+where
+    R: requests::SlackWebRequestSender,
+    T: DeserializeOwned,
+    Error: From<R::Error>,
+{
+    let (status, body) = send(client, method, params)?;
+    decode(method, status, &body)
+}
+
+/// Like `call`, but a response with `"ok": false` and the error code
+/// `allowed` is `Ok(None)` rather than an error.
+pub(crate) fn call_allowing<R, T>(
+    client: &R,
+    method: &str,
+    params: &[(&str, &str)],
+    allowed: &str,
+) -> Result<Option<T>, Error>
+where
+    R: requests::SlackWebRequestSender,
+    T: DeserializeOwned,
+    Error: From<R::Error>,
+{
+    let (status, body) = send(client, method, params)?;
+    if !status.ok && status.error.as_deref() == Some(allowed) {
+        return Ok(None);
+    }
+    decode(method, status, &body).map(Some)
+}
+
+fn send<R>(client: &R, method: &str, params: &[(&str, &str)]) -> Result<(Status, String), Error>
+where
+    R: requests::SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let url = format!("https://slack.com/api/{}", method);
+    let body = client.send(&url, params)?;
+    Ok((serde_json::from_str(&body)?, body))
+}
+
+fn decode<T: DeserializeOwned>(method: &str, status: Status, body: &str) -> Result<T, Error> {
+    if !status.ok {
+        return Err(Error::Slack {
+            method: method.into(),
+            code: status.error.unwrap_or_else(|| "unknown_error".into()),
+        });
+    }
+    Ok(serde_json::from_str(body)?)
+}
+
+/// Formats a boolean the way the Web API expects it as a parameter
+pub(crate) fn flag(value: bool) -> &'static str {
+    if value {
+        "1"
+    } else {
+        "0"
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::requests::SlackWebRequestSender;
+    use std::borrow::Borrow;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::io;
+
+    /// A method url and its parameters
+    pub type Request = (String, Vec<(String, String)>);
+
+    /// A `SlackWebRequestSender` answering with canned responses and
+    /// recording the requests it was given
+    #[derive(Default)]
+    pub struct MockSender {
+        responses: RefCell<VecDeque<String>>,
+        requests: RefCell<Vec<Request>>,
+    }
+
+    impl MockSender {
+        pub fn new(responses: &[&str]) -> MockSender {
+            MockSender {
+                responses: RefCell::new(responses.iter().map(|r| r.to_string()).collect()),
+                ..Default::default()
+            }
+        }
+
+        /// The method urls and parameters sent so far
+        pub fn requests(&self) -> Vec<Request> {
+            self.requests.borrow().clone()
+        }
+
+        /// The value of `name` in the `n`th request
+        pub fn param(&self, n: usize, name: &str) -> Option<String> {
+            self.requests.borrow()[n]
+                .1
+                .iter()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.clone())
+        }
+    }
+
+    impl SlackWebRequestSender for MockSender {
+        type Error = io::Error;
+
+        fn send<I, K, V, S>(&self, method: S, params: I) -> Result<String, io::Error>
+        where
+            I: IntoIterator + Send,
+            K: AsRef<str>,
+            V: AsRef<str>,
+            I::Item: Borrow<(K, V)>,
+            S: AsRef<str> + Send,
+        {
+            let params = params
+                .into_iter()
+                .map(|p| {
+                    let (k, v) = p.borrow();
+                    (k.as_ref().to_string(), v.as_ref().to_string())
+                })
+                .collect();
+            self.requests
+                .borrow_mut()
+                .push((method.as_ref().to_string(), params));
+            self.responses
+                .borrow_mut()
+                .pop_front()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "no canned response"))
+        }
+    }
+}
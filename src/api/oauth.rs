@@ -0,0 +1,150 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub use slack_api::sync::oauth::*;
+
+use crate::api::call;
+use crate::api::requests::SlackWebRequestSender;
+use crate::error::Error;
+
+#[derive(Clone, Default, Debug)]
+pub struct V2ExchangeRequest<'a> {
+    /// Issued when you created your application.
+    pub client_id: &'a str,
+    /// Issued when you created your application.
+    pub client_secret: &'a str,
+    /// `authorization_code` (the default) or `refresh_token`.
+    pub grant_type: Option<&'a str>,
+    /// The code from the OAuth redirect, for `authorization_code`.
+    pub code: Option<&'a str>,
+    /// Must match the `redirect_uri` of the authorization request, if one was given.
+    pub redirect_uri: Option<&'a str>,
+    /// The refresh token from a previous exchange, for `refresh_token`.
+    pub refresh_token: Option<&'a str>,
+}
+
+/// The workspace a token was issued for
+#[derive(Clone, Debug, Deserialize)]
+pub struct V2Team {
+    pub id: String,
+    pub name: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct V2ExchangeResponse {
+    pub access_token: String,
+    pub token_type: Option<String>,
+    pub scope: Option<String>,
+    pub bot_user_id: Option<String>,
+    pub app_id: Option<String>,
+    pub team: Option<V2Team>,
+    /// Set when token rotation is enabled, to pass to the next exchange
+    pub refresh_token: Option<String>,
+    /// Seconds until `access_token` expires, when token rotation is enabled
+    pub expires_in: Option<u64>,
+}
+
+/// Exchanges an OAuth code, or a refresh token when token rotation is
+/// enabled, for an access token. Needs no token itself.
+///
+/// Wraps https://api.slack.com/methods/oauth.v2.access
+pub fn v2_exchange<R>(
+    client: &R,
+    request: &V2ExchangeRequest<'_>,
+) -> Result<V2ExchangeResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let params = vec![
+        Some(("client_id", request.client_id)),
+        Some(("client_secret", request.client_secret)),
+        request
+            .grant_type
+            .map(|grant_type| ("grant_type", grant_type)),
+        request.code.map(|code| ("code", code)),
+        request
+            .redirect_uri
+            .map(|redirect_uri| ("redirect_uri", redirect_uri)),
+        request
+            .refresh_token
+            .map(|refresh_token| ("refresh_token", refresh_token)),
+    ];
+    let params = params.into_iter().flatten().collect::<Vec<_>>();
+    call(client, "oauth.v2.access", &params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mock::MockSender;
+
+    #[test]
+    fn refresh_rotating_token() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "app_id": "A0KRD7HC3",
+            "scope": "chat:write,channels:history",
+            "token_type": "bot",
+            "access_token": "xoxe.xoxb-1-new",
+            "bot_user_id": "U0KRQLJ9H",
+            "refresh_token": "xoxe-1-next",
+            "expires_in": 43200,
+            "team": {"id": "T9TK3CUKW", "name": "Slack Softball Team"},
+            "enterprise": null,
+            "is_enterprise_install": false
+        }"#]);
+        let response = v2_exchange(
+            &client,
+            &V2ExchangeRequest {
+                client_id: "4b39e9-752c4",
+                client_secret: "33fea0113f5b1",
+                grant_type: Some("refresh_token"),
+                refresh_token: Some("xoxe-1-current"),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(response.access_token, "xoxe.xoxb-1-new");
+        assert_eq!(response.refresh_token.unwrap(), "xoxe-1-next");
+        assert_eq!(response.expires_in, Some(43200));
+        assert_eq!(response.team.unwrap().id, "T9TK3CUKW");
+        assert_eq!(
+            client.requests()[0].0,
+            "https://slack.com/api/oauth.v2.access"
+        );
+        assert_eq!(client.param(0, "grant_type").unwrap(), "refresh_token");
+        assert_eq!(client.param(0, "refresh_token").unwrap(), "xoxe-1-current");
+        assert!(client.param(0, "code").is_none());
+        assert!(client.param(0, "token").is_none());
+    }
+
+    #[test]
+    fn invalid_refresh_token() {
+        let client = MockSender::new(&[r#"{"ok": false, "error": "invalid_refresh_token"}"#]);
+        let err = v2_exchange(
+            &client,
+            &V2ExchangeRequest {
+                client_id: "4b39e9-752c4",
+                client_secret: "33fea0113f5b1",
+                grant_type: Some("refresh_token"),
+                refresh_token: Some("xoxe-1-stale"),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), Some("invalid_refresh_token"));
+    }
+}
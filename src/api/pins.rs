@@ -0,0 +1,102 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub use slack_api::sync::pins::*;
+
+use crate::api::requests::SlackWebRequestSender;
+use crate::api::{call, Item};
+use crate::error::Error;
+
+/// Shadows `slack_api`'s version to decode the items as the general `Item`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ListResponse {
+    #[serde(default)]
+    pub items: Vec<Item>,
+}
+
+/// Lists items pinned to a channel.
+///
+/// Wraps https://api.slack.com/methods/pins.list
+pub fn list<R>(client: &R, token: &str, request: &ListRequest<'_>) -> Result<ListResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let params = [("token", token), ("channel", request.channel)];
+    call(client, "pins.list", &params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mock::MockSender;
+    use crate::MessageExt;
+
+    #[test]
+    fn list_message_and_file() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "items": [
+                {
+                    "type": "message",
+                    "channel": "C2U86NC6H",
+                    "created": 1508881078,
+                    "created_by": "U2U85N1RZ",
+                    "message": {
+                        "type": "message",
+                        "user": "U2U85N1RZ",
+                        "text": "Test message",
+                        "ts": "1508284197.000015",
+                        "permalink": "https://example.slack.com/archives/C2U86NC6H/p1508284197000015"
+                    }
+                },
+                {
+                    "type": "file",
+                    "created": 1508880991,
+                    "created_by": "U2U85N1RZ",
+                    "file": {
+                        "id": "F7PKF2B53",
+                        "name": "digest.txt",
+                        "title": "Digest"
+                    }
+                }
+            ]
+        }"#]);
+        let response = list(
+            &client,
+            "xoxb-token",
+            &ListRequest {
+                channel: "C2U86NC6H",
+            },
+        )
+        .unwrap();
+        match response.items[0] {
+            Item::Message {
+                ref channel,
+                ref message,
+                ..
+            } => {
+                assert_eq!(channel, "C2U86NC6H");
+                assert_eq!(message.as_ref().unwrap().text(), Some("Test message"));
+            }
+            ref item => panic!("unexpected item: {:?}", item),
+        }
+        match response.items[1] {
+            Item::File { ref file } => assert_eq!(file.id(), Some("F7PKF2B53")),
+            ref item => panic!("unexpected item: {:?}", item),
+        }
+        assert_eq!(client.param(0, "channel").unwrap(), "C2U86NC6H");
+    }
+}
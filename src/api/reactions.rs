@@ -0,0 +1,274 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+pub use slack_api::sync::reactions::*;
+
+use crate::api::requests::SlackWebRequestSender;
+use crate::api::{Message, Timestamp};
+use crate::error::Error;
+use crate::MessageExt;
+
+/// What `add` did, telling a reaction that was already there apart from
+/// failures
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddOutcome {
+    Added,
+    /// The item already had this reaction from the user
+    AlreadyReacted,
+}
+
+/// What `remove` did, telling a reaction that wasn't there apart from
+/// failures
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RemoveOutcome {
+    Removed,
+    /// The item didn't have this reaction from the user
+    NoReaction,
+}
+
+/// Adds a reaction to an item.
+///
+/// Wraps https://api.slack.com/methods/reactions.add
+pub fn add<R>(client: &R, token: &str, request: &AddRequest<'_>) -> Result<AddOutcome, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    match slack_api::sync::reactions::add(client, token, request) {
+        Ok(_) => Ok(AddOutcome::Added),
+        Err(AddError::AlreadyReacted) => Ok(AddOutcome::AlreadyReacted),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Removes a reaction from an item.
+///
+/// Wraps https://api.slack.com/methods/reactions.remove
+pub fn remove<R>(
+    client: &R,
+    token: &str,
+    request: &RemoveRequest<'_>,
+) -> Result<RemoveOutcome, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    match slack_api::sync::reactions::remove(client, token, request) {
+        Ok(_) => Ok(RemoveOutcome::Removed),
+        Err(RemoveError::NoReaction) => Ok(RemoveOutcome::NoReaction),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Adds a reaction to the message a permalink points at.
+///
+/// Accepts `/archives/<channel>/p<ts>` permalinks. The older
+/// `/channels/<name>/p<ts>` form names the channel instead of giving its id,
+/// which `reactions.add` needs, and is rejected.
+pub fn add_to_permalink<R>(
+    client: &R,
+    token: &str,
+    name: &str,
+    permalink: &str,
+) -> Result<AddOutcome, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let (channel, timestamp) = parse_permalink(permalink)
+        .ok_or_else(|| Error::Internal(format!("Invalid message permalink: {}", permalink)))?;
+    let request = AddRequest {
+        name,
+        channel: Some(channel),
+        timestamp: Some(timestamp),
+        ..Default::default()
+    };
+    add(client, token, &request)
+}
+
+/// Adds a reaction to a received message, e.g. one from `Event::Message`.
+///
+/// `name` may be given with or without the surrounding colons.
+pub fn add_to_message<R>(
+    client: &R,
+    token: &str,
+    name: &str,
+    msg: &Message,
+) -> Result<AddOutcome, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let channel = msg
+        .channel()
+        .ok_or_else(|| Error::Internal("Message has no channel to react in".into()))?;
+    let timestamp = msg
+        .ts()
+        .ok_or_else(|| Error::Internal("Message has no ts to react to".into()))?;
+    let request = AddRequest {
+        name: name.trim_matches(':'),
+        channel: Some(channel),
+        timestamp: Some(timestamp),
+        ..Default::default()
+    };
+    add(client, token, &request)
+}
+
+/// Extracts the channel id and message timestamp from a Slack message
+/// permalink of the `/archives/<channel>/p<ts>` form
+pub fn parse_permalink(permalink: &str) -> Option<(&str, Timestamp)> {
+    let path = permalink.split(['?', '#']).next()?;
+    let mut segments = path.trim_end_matches('/').rsplit('/');
+    let digits = segments.next()?.strip_prefix('p')?;
+    let channel = segments.next().filter(|c| !c.is_empty())?;
+    if segments.next()? != "archives" {
+        return None;
+    }
+    if digits.len() <= 6 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (secs, micros) = digits.split_at(digits.len() - 6);
+    let timestamp = crate::timestamp::from_parts(secs.parse().ok()?, micros.parse().ok()?);
+    Some((channel, timestamp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mock::MockSender;
+
+    #[test]
+    fn parse_archives_permalink() {
+        let (channel, ts) =
+            parse_permalink("https://example.slack.com/archives/C024BE91L/p1355517523000008")
+                .unwrap();
+        assert_eq!(channel, "C024BE91L");
+        assert_eq!(ts.to_param_value(), "1355517523.000008");
+    }
+
+    #[test]
+    fn parse_threaded_archives_permalink() {
+        let (channel, ts) = parse_permalink(
+            "https://example.slack.com/archives/C024BE91L/p1355517523123456?thread_ts=1355517500.000001&cid=C024BE91L",
+        )
+        .unwrap();
+        assert_eq!(channel, "C024BE91L");
+        assert_eq!(ts.to_param_value(), "1355517523.123456");
+    }
+
+    #[test]
+    fn parse_permalink_keeps_precision() {
+        let (_, ts) =
+            parse_permalink("https://example.slack.com/archives/C024BE91L/p1588859442000249")
+                .unwrap();
+        assert_eq!(ts.to_param_value(), "1588859442.000249");
+    }
+
+    #[test]
+    fn parse_legacy_channels_permalink() {
+        // names the channel, which `reactions.add` can't use
+        assert!(
+            parse_permalink("https://example.slack.com/channels/general/p1355517523000008")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn parse_invalid_permalink() {
+        assert!(parse_permalink("https://example.slack.com/archives/C024BE91L").is_none());
+        assert!(
+            parse_permalink("https://example.slack.com/files/U1/F1/p1355517523000008").is_none()
+        );
+        assert!(parse_permalink("https://example.slack.com/archives/C024BE91L/pabc").is_none());
+    }
+
+    fn add_thumbsup(client: &MockSender) -> Result<AddOutcome, Error> {
+        add(
+            client,
+            "xoxb-token",
+            &AddRequest {
+                name: "thumbsup",
+                channel: Some("C024BE91L"),
+                timestamp: Some(Timestamp::from((1355517523, 0.000008))),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn add_outcomes() {
+        let client = MockSender::new(&[
+            r#"{"ok": true}"#,
+            r#"{"ok": false, "error": "already_reacted"}"#,
+            r#"{"ok": false, "error": "message_not_found"}"#,
+        ]);
+        assert_eq!(add_thumbsup(&client).unwrap(), AddOutcome::Added);
+        assert_eq!(add_thumbsup(&client).unwrap(), AddOutcome::AlreadyReacted);
+        assert!(add_thumbsup(&client).is_err());
+    }
+
+    #[test]
+    fn add_to_received_message() {
+        let client = MockSender::new(&[r#"{"ok": true}"#]);
+        let msg: Message = serde_json::from_str(
+            r#"{
+            "type": "message",
+            "channel": "C061EG9SL",
+            "user": "U061F7AUR",
+            "text": "ship it",
+            "ts": "1483037603.017503"
+        }"#,
+        )
+        .unwrap();
+        let outcome = add_to_message(&client, "xoxb-token", ":rocket:", &msg).unwrap();
+        assert_eq!(outcome, AddOutcome::Added);
+        assert_eq!(client.param(0, "name").unwrap(), "rocket");
+        assert_eq!(client.param(0, "channel").unwrap(), "C061EG9SL");
+        assert_eq!(client.param(0, "timestamp").unwrap(), "1483037603.017503");
+    }
+
+    #[test]
+    fn add_to_message_without_channel() {
+        let client = MockSender::new(&[]);
+        let msg: Message =
+            serde_json::from_str(r#"{"type": "message", "text": "hi", "ts": "1483037603.017503"}"#)
+                .unwrap();
+        assert!(add_to_message(&client, "xoxb-token", "wave", &msg).is_err());
+        assert!(client.requests().is_empty());
+    }
+
+    #[test]
+    fn remove_outcomes() {
+        let client = MockSender::new(&[
+            r#"{"ok": true}"#,
+            r#"{"ok": false, "error": "no_reaction"}"#,
+        ]);
+        let request = RemoveRequest {
+            name: "thumbsup",
+            file: Some("F0HS27V1Z"),
+            ..Default::default()
+        };
+        assert_eq!(
+            remove(&client, "xoxb-token", &request).unwrap(),
+            RemoveOutcome::Removed
+        );
+        assert_eq!(
+            remove(&client, "xoxb-token", &request).unwrap(),
+            RemoveOutcome::NoReaction
+        );
+        assert_eq!(client.param(1, "file").unwrap(), "F0HS27V1Z");
+    }
+}
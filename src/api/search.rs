@@ -0,0 +1,120 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub use slack_api::sync::search::*;
+
+use crate::api::requests::SlackWebRequestSender;
+use crate::api::Message;
+use crate::error::Error;
+
+/// The largest page size the `search.*` methods accept
+pub const MAX_COUNT: u32 = 100;
+
+/// Searches for messages matching a query, following `paging` through every
+/// result page and returning all matches.
+///
+/// `request.count` is the page size and is clamped to `1..=MAX_COUNT`
+/// rather than being rejected by Slack. Pages start at `request.page`, or
+/// the first page if unset.
+pub fn messages_all<R>(
+    client: &R,
+    token: &str,
+    request: &MessagesRequest<'_>,
+) -> Result<Vec<Message>, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let mut request = request.clone();
+    request.count = Some(request.count.unwrap_or(MAX_COUNT).clamp(1, MAX_COUNT));
+    let mut page = request.page.unwrap_or(1).max(1);
+    let mut all = Vec::new();
+    loop {
+        request.page = Some(page);
+        let response = messages(client, token, &request)?;
+        let messages = match response.messages {
+            Some(messages) => messages,
+            None => break,
+        };
+        all.extend(messages.matches.unwrap_or_default());
+        let pages = messages.paging.and_then(|p| p.pages).unwrap_or(0);
+        if i64::from(page) >= i64::from(pages) {
+            break;
+        }
+        page += 1;
+    }
+    Ok(all)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mock::MockSender;
+    use crate::MessageExt;
+
+    fn page(text: &str, page: u32, pages: u32) -> String {
+        format!(
+            r#"{{"ok": true, "query": "deploy", "messages": {{
+                "matches": [{{"type": "message", "ts": "1.000001", "text": "{}"}}],
+                "paging": {{"count": 1, "page": {}, "pages": {}, "total": {}}},
+                "total": {}
+            }}}}"#,
+            text, page, pages, pages, pages
+        )
+    }
+
+    #[test]
+    fn messages_all_follows_paging() {
+        let (first, second) = (page("one", 1, 2), page("two", 2, 2));
+        let client = MockSender::new(&[&first, &second]);
+        let request = MessagesRequest {
+            query: "deploy",
+            ..Default::default()
+        };
+        let matches = messages_all(&client, "xoxb", &request).unwrap();
+        let texts = matches.iter().map(|m| m.text()).collect::<Vec<_>>();
+        assert_eq!(texts, vec![Some("one"), Some("two")]);
+        assert_eq!(client.param(0, "page").as_deref(), Some("1"));
+        assert_eq!(client.param(1, "page").as_deref(), Some("2"));
+        assert_eq!(client.param(0, "count").as_deref(), Some("100"));
+    }
+
+    #[test]
+    fn messages_all_clamps_count() {
+        let only = page("one", 1, 1);
+        let client = MockSender::new(&[&only, &only]);
+        let mut request = MessagesRequest {
+            query: "deploy",
+            count: Some(1000),
+            ..Default::default()
+        };
+        messages_all(&client, "xoxb", &request).unwrap();
+        request.count = Some(0);
+        messages_all(&client, "xoxb", &request).unwrap();
+        assert_eq!(client.param(0, "count").as_deref(), Some("100"));
+        assert_eq!(client.param(1, "count").as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn messages_all_error() {
+        let client = MockSender::new(&[r#"{"ok": false, "error": "not_authed"}"#]);
+        let request = MessagesRequest {
+            query: "deploy",
+            ..Default::default()
+        };
+        let err = messages_all(&client, "xoxb", &request).unwrap_err();
+        assert_eq!(err.code(), Some("not_authed"));
+    }
+}
@@ -0,0 +1,307 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub use slack_api::sync::team::*;
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::api::call;
+use crate::api::requests::SlackWebRequestSender;
+use crate::error::Error;
+
+/// Billing status of a single user
+#[derive(Clone, Debug, Deserialize)]
+pub struct BillableInfo {
+    pub billing_active: bool,
+}
+
+/// Shadows `slack_api`'s version, which expects a plain `bool` per user and
+/// so fails to decode the `{"billing_active": ...}` objects Slack sends.
+#[derive(Clone, Debug, Deserialize)]
+pub struct BillableInfoResponse {
+    /// Billing status keyed by user id
+    pub billable_info: HashMap<String, BillableInfo>,
+}
+
+/// Gets billable users information for the current team.
+///
+/// Wraps https://api.slack.com/methods/team.billableInfo
+pub fn billable_info<R>(
+    client: &R,
+    token: &str,
+    request: &BillableInfoRequest<'_>,
+) -> Result<BillableInfoResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let params = vec![
+        Some(("token", token)),
+        request.user.map(|user| ("user", user)),
+    ];
+    let params = params.into_iter().flatten().collect::<Vec<_>>();
+    call(client, "team.billableInfo", &params)
+}
+
+/// Decodes a flag Slack sends either as a boolean or as `0`/`1`
+fn lenient_bool<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(
+        match Option::<serde_json::Value>::deserialize(deserializer)? {
+            Some(serde_json::Value::Bool(b)) => Some(b),
+            Some(serde_json::Value::Number(n)) => Some(n.as_i64() != Some(0)),
+            _ => None,
+        },
+    )
+}
+
+/// A custom profile field defined for the team
+#[derive(Clone, Debug, Deserialize)]
+pub struct ProfileField {
+    pub id: String,
+    pub label: String,
+    /// `text`, `date`, `link`, `options_list` or `user`
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub hint: Option<String>,
+    pub ordering: Option<i32>,
+    /// Choices of an `options_list` field
+    pub possible_values: Option<Vec<String>>,
+    pub options: Option<HashMap<String, serde_json::Value>>,
+    #[serde(default, deserialize_with = "lenient_bool")]
+    pub is_hidden: Option<bool>,
+    /// The `ProfileSection` the field is shown in
+    pub section_id: Option<String>,
+}
+
+/// A section grouping profile fields
+#[derive(Clone, Debug, Deserialize)]
+pub struct ProfileSection {
+    pub id: String,
+    pub label: Option<String>,
+    pub section_type: Option<String>,
+    pub order: Option<i32>,
+    #[serde(default, deserialize_with = "lenient_bool")]
+    pub is_hidden: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Profile {
+    #[serde(default)]
+    pub fields: Vec<ProfileField>,
+    #[serde(default)]
+    pub sections: Vec<ProfileSection>,
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct ProfileGetRequest<'a> {
+    /// Filter by visibility, e.g. `all`, `visible` or `hidden`.
+    pub visibility: Option<&'a str>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ProfileGetResponse {
+    pub profile: Profile,
+}
+
+/// Retrieves the team's profile field definitions, needed to interpret the
+/// custom `fields` of a user's profile.
+///
+/// `api::team_profile::get` wraps the same method but fails to decode the
+/// `0`/`1` flags and option values Slack sends.
+///
+/// Wraps https://api.slack.com/methods/team.profile.get
+pub fn profile_get<R>(
+    client: &R,
+    token: &str,
+    request: &ProfileGetRequest<'_>,
+) -> Result<ProfileGetResponse, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let params = vec![
+        Some(("token", token)),
+        request
+            .visibility
+            .map(|visibility| ("visibility", visibility)),
+    ];
+    let params = params.into_iter().flatten().collect::<Vec<_>>();
+    call(client, "team.profile.get", &params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mock::MockSender;
+
+    #[test]
+    fn billable_info_of_user() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "billable_info": {"U02UCPE1R": {"billing_active": true}}
+        }"#]);
+        let response = billable_info(
+            &client,
+            "xoxp-token",
+            &BillableInfoRequest {
+                user: Some("U02UCPE1R"),
+            },
+        )
+        .unwrap();
+        assert!(response.billable_info["U02UCPE1R"].billing_active);
+        assert_eq!(client.param(0, "user").unwrap(), "U02UCPE1R");
+    }
+
+    #[test]
+    fn access_logs_page() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "logins": [{
+                "user_id": "U45678",
+                "username": "alice",
+                "date_first": 1422922864,
+                "date_last": 1422922864,
+                "count": 1,
+                "ip": "127.0.0.1",
+                "user_agent": "SlackWeb Mozilla/5.0",
+                "isp": "BigCo ISP",
+                "country": "US",
+                "region": "CA"
+            }],
+            "paging": {"count": 100, "total": 2, "page": 1, "pages": 1}
+        }"#]);
+        let response = access_logs(
+            &client,
+            "xoxp-token",
+            &AccessLogsRequest {
+                count: Some(100),
+                page: Some(1),
+                before: None,
+            },
+        )
+        .unwrap();
+        let logins = response.logins.unwrap();
+        assert_eq!(logins[0].user_id.as_deref(), Some("U45678"));
+        assert_eq!(client.param(0, "count").unwrap(), "100");
+        assert_eq!(client.param(0, "page").unwrap(), "1");
+    }
+
+    #[test]
+    fn profile_get_fields() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "profile": {
+                "fields": [
+                    {
+                        "id": "Xf06054AAA",
+                        "ordering": 0,
+                        "label": "Phone extension",
+                        "hint": "Enter the extension to reach your desk",
+                        "type": "text",
+                        "possible_values": null,
+                        "options": {"is_scim": false, "is_protected": false},
+                        "is_hidden": 1,
+                        "section_id": "123ABC"
+                    },
+                    {
+                        "id": "Xf06054BBB",
+                        "ordering": 1,
+                        "label": "Team",
+                        "type": "options_list",
+                        "possible_values": ["Eng", "Sales"]
+                    }
+                ],
+                "sections": [
+                    {"id": "123ABC", "label": "Contact", "section_type": "custom", "order": 1, "is_hidden": false}
+                ]
+            }
+        }"#]);
+        let response = profile_get(
+            &client,
+            "xoxp-token",
+            &ProfileGetRequest {
+                visibility: Some("all"),
+            },
+        )
+        .unwrap();
+        let fields = &response.profile.fields;
+        assert_eq!(fields[0].label, "Phone extension");
+        assert_eq!(fields[0].is_hidden, Some(true));
+        assert_eq!(fields[1].ty, "options_list");
+        assert_eq!(fields[1].is_hidden, None);
+        assert_eq!(response.profile.sections[0].id, "123ABC");
+        assert_eq!(
+            client.requests()[0].0,
+            "https://slack.com/api/team.profile.get"
+        );
+        assert_eq!(client.param(0, "visibility").unwrap(), "all");
+    }
+
+    #[test]
+    fn integration_logs_entries() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "logs": [
+                {
+                    "app_id": "A012B3C4D",
+                    "app_type": "slack_app",
+                    "user_id": "U1234ABCD",
+                    "user_name": "Roger",
+                    "date": "1392163200",
+                    "change_type": "added",
+                    "scope": "incoming-webhook"
+                },
+                {
+                    "service_id": "1234567890",
+                    "service_type": "Google Calendar",
+                    "user_id": "U1234ABCD",
+                    "user_name": "Johnny",
+                    "channel": "C1234ABCD",
+                    "date": "1392163200",
+                    "change_type": "removed",
+                    "reason": "user"
+                }
+            ],
+            "paging": {"count": 2, "total": 2, "page": 1, "pages": 1}
+        }"#]);
+        let response = integration_logs(
+            &client,
+            "xoxp-token",
+            &IntegrationLogsRequest {
+                user: Some("U1234ABCD"),
+                count: Some(2),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let logs = response.logs.unwrap();
+        assert_eq!(logs[0].app_id.as_deref(), Some("A012B3C4D"));
+        assert_eq!(logs[0].change_type.as_deref(), Some("added"));
+        assert_eq!(logs[1].service_type.as_deref(), Some("Google Calendar"));
+        assert_eq!(logs[1].date.as_deref(), Some("1392163200"));
+        assert_eq!(
+            client.requests()[0].0,
+            "https://slack.com/api/team.integrationLogs"
+        );
+        assert_eq!(client.param(0, "user").unwrap(), "U1234ABCD");
+        assert_eq!(client.param(0, "count").unwrap(), "2");
+        assert!(client.param(0, "page").is_none());
+    }
+}
@@ -0,0 +1,109 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub use slack_api::sync::users::*;
+
+use crate::api::call;
+use crate::api::requests::SlackWebRequestSender;
+use crate::error::Error;
+
+/// A user's custom status
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct Status {
+    #[serde(default)]
+    pub status_text: String,
+    #[serde(default)]
+    pub status_emoji: String,
+    /// Unix time at which the status is cleared, `0` if it never expires
+    #[serde(default)]
+    pub status_expiration: u64,
+}
+
+#[derive(Deserialize)]
+struct SetStatusResponse {
+    profile: Status,
+}
+
+/// Sets the status of the token's user, returning the status Slack stored.
+///
+/// Pass empty strings to clear the status. `status_expiration` is a unix
+/// time; `None` keeps the status until it is changed.
+///
+/// Wraps https://api.slack.com/methods/users.profile.set
+pub fn set_status<R>(
+    client: &R,
+    token: &str,
+    status_text: &str,
+    status_emoji: &str,
+    status_expiration: Option<u64>,
+) -> Result<Status, Error>
+where
+    R: SlackWebRequestSender,
+    Error: From<R::Error>,
+{
+    let profile = serde_json::json!({
+        "status_text": status_text,
+        "status_emoji": status_emoji,
+        "status_expiration": status_expiration.unwrap_or(0),
+    })
+    .to_string();
+    let params = [("token", token), ("profile", profile.as_str())];
+    let response: SetStatusResponse = call(client, "users.profile.set", &params)?;
+    Ok(response.profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mock::MockSender;
+
+    #[test]
+    fn set_status_packs_profile() {
+        let client = MockSender::new(&[r#"{
+            "ok": true,
+            "profile": {
+                "real_name": "Egon Spengler",
+                "status_text": "riding a train",
+                "status_emoji": ":mountain_railway:",
+                "status_expiration": 1532627506
+            }
+        }"#]);
+        let status = set_status(
+            &client,
+            "xoxp-token",
+            "riding a train",
+            ":mountain_railway:",
+            Some(1532627506),
+        )
+        .unwrap();
+        assert_eq!(status.status_emoji, ":mountain_railway:");
+        assert_eq!(status.status_expiration, 1532627506);
+        assert_eq!(
+            client.requests()[0].0,
+            "https://slack.com/api/users.profile.set"
+        );
+        let profile: serde_json::Value =
+            serde_json::from_str(&client.param(0, "profile").unwrap()).unwrap();
+        assert_eq!(profile["status_text"], "riding a train");
+        assert_eq!(profile["status_expiration"], 1532627506);
+    }
+
+    #[test]
+    fn set_status_error() {
+        let client = MockSender::new(&[r#"{"ok": false, "error": "too_long"}"#]);
+        let err = set_status(&client, "xoxp-token", "x", ":x:", None).unwrap_err();
+        assert_eq!(err.code(), Some("too_long"));
+    }
+}
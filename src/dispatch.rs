@@ -0,0 +1,180 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Routing of events to closures, as an alternative to implementing
+//! `EventHandler` on a struct.
+
+use crate::api::Message;
+use crate::{Event, EventHandler, RtmClient};
+
+type Callback<'a, T> = Box<dyn FnMut(&RtmClient, &T) + 'a>;
+type Notify<'a> = Box<dyn FnMut(&RtmClient) + 'a>;
+
+/// An `EventHandler` calling the closures registered for each kind of event.
+///
+/// ```no_run
+/// # use slack::{Dispatcher, MessageExt, RtmClient};
+/// let mut handler = Dispatcher::new()
+///     .on_message(|_cli, msg| println!("{:?}", msg.text()))
+///     .on_any(|_cli, event| println!("{:?}", event));
+/// RtmClient::login_and_run("xoxb-token", &mut handler).unwrap();
+/// ```
+#[derive(Default)]
+pub struct Dispatcher<'a> {
+    message: Option<Callback<'a, Message>>,
+    reaction_added: Option<Callback<'a, Event>>,
+    any: Option<Callback<'a, Event>>,
+    connect: Option<Notify<'a>>,
+    close: Option<Notify<'a>>,
+}
+
+impl<'a> Dispatcher<'a> {
+    /// A dispatcher ignoring every event
+    pub fn new() -> Dispatcher<'a> {
+        Dispatcher::default()
+    }
+
+    /// Calls `f` for each `Event::Message`
+    pub fn on_message<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&RtmClient, &Message) + 'a,
+    {
+        self.message = Some(Box::new(f));
+        self
+    }
+
+    /// Calls `f` for each `Event::ReactionAdded`
+    pub fn on_reaction_added<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&RtmClient, &Event) + 'a,
+    {
+        self.reaction_added = Some(Box::new(f));
+        self
+    }
+
+    /// Calls `f` for each event that has no more specific closure registered
+    pub fn on_any<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&RtmClient, &Event) + 'a,
+    {
+        self.any = Some(Box::new(f));
+        self
+    }
+
+    /// Calls `f` when the connection is opened
+    pub fn on_connect<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&RtmClient) + 'a,
+    {
+        self.connect = Some(Box::new(f));
+        self
+    }
+
+    /// Calls `f` when the connection is closed
+    pub fn on_close<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&RtmClient) + 'a,
+    {
+        self.close = Some(Box::new(f));
+        self
+    }
+}
+
+impl<'a> EventHandler for Dispatcher<'a> {
+    fn on_event(&mut self, cli: &RtmClient, event: Event) {
+        match (event, &mut self.message, &mut self.reaction_added) {
+            (Event::Message(ref msg), &mut Some(ref mut f), _) => f(cli, msg),
+            (ref event @ Event::ReactionAdded { .. }, _, &mut Some(ref mut f)) => f(cli, event),
+            (ref event, _, _) => {
+                if let Some(ref mut f) = self.any {
+                    f(cli, event)
+                }
+            }
+        }
+    }
+
+    fn on_close(&mut self, cli: &RtmClient) {
+        if let Some(ref mut f) = self.close {
+            f(cli)
+        }
+    }
+
+    fn on_connect(&mut self, cli: &RtmClient) {
+        if let Some(ref mut f) = self.connect {
+            f(cli)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::local_client;
+    use crate::MessageExt;
+
+    fn event(json: &str) -> Event {
+        Event::from_json(json).unwrap()
+    }
+
+    #[test]
+    fn routes_by_event_type() {
+        let cli = local_client();
+        let mut texts = Vec::new();
+        let mut reactions = 0;
+        let mut others = Vec::new();
+        {
+            let mut dispatcher = Dispatcher::new()
+                .on_message(|_, msg| texts.push(msg.text().unwrap_or_default().to_string()))
+                .on_reaction_added(|_, _| reactions += 1)
+                .on_any(|_, event| others.push(format!("{:?}", event)));
+            dispatcher.on_event(
+                &cli,
+                event(r#"{"type": "message", "channel": "C1", "text": "hi", "ts": "1.000001"}"#),
+            );
+            dispatcher.on_event(
+                &cli,
+                event(
+                    r#"{
+                    "type": "reaction_added",
+                    "user": "U024BE7LH",
+                    "reaction": "thumbsup",
+                    "item": {"type": "message", "channel": "C1", "ts": "1.000001"},
+                    "event_ts": "1.000002"
+                }"#,
+                ),
+            );
+            dispatcher.on_event(&cli, event(r#"{"type": "hello"}"#));
+        }
+        assert_eq!(texts, vec!["hi"]);
+        assert_eq!(reactions, 1);
+        assert_eq!(others, vec!["Hello"]);
+    }
+
+    #[test]
+    fn falls_back_to_any() {
+        let cli = local_client();
+        let mut seen = 0;
+        {
+            let mut dispatcher = Dispatcher::new().on_any(|_, _| seen += 1);
+            dispatcher.on_event(
+                &cli,
+                event(r#"{"type": "message", "channel": "C1", "text": "hi", "ts": "1.000001"}"#),
+            );
+            EventHandler::on_close(&mut dispatcher, &cli);
+        }
+        assert_eq!(seen, 1);
+    }
+}
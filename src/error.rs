@@ -33,12 +33,71 @@ pub enum Error {
     Url(::url::ParseError),
     /// Error decoding Json
     Json(::serde_json::Error),
+    /// IO error, e.g. configuring the websocket's TCP stream
+    Io(io::Error),
+    /// An acknowledgement of a sent message that could not be decoded
+    MalformedAck(::serde_json::Error),
     /// Slack Api Error
     Api(String),
+    /// A Slack Web API method answered `"ok": false` with an error code
+    Slack {
+        /// The method that was called, e.g. `chat.postMessage`
+        method: String,
+        /// The error code, e.g. `channel_not_found`
+        code: String,
+    },
     /// Errors that do not fit under the other types, Internal is for EG channel errors.
     Internal(String),
 }
 
+impl Error {
+    /// Returns true if the websocket was closed, either by Slack or by us.
+    ///
+    /// Useful to tell a dropped connection, which can be retried, apart from
+    /// other failures.
+    pub fn is_connection_closed(&self) -> bool {
+        matches!(
+            *self,
+            Error::WebSocket(::tungstenite::Error::ConnectionClosed)
+                | Error::WebSocket(::tungstenite::Error::AlreadyClosed)
+        )
+    }
+
+    /// Returns true if the error is an IO failure of the websocket, e.g. a
+    /// timeout or a failed DNS lookup.
+    pub fn is_websocket_io(&self) -> bool {
+        matches!(*self, Error::WebSocket(::tungstenite::Error::Io(_)))
+    }
+
+    /// Returns true if the request or connection failed in transit, e.g. a
+    /// failed DNS lookup or TLS handshake, so trying again may succeed.
+    pub fn is_transient(&self) -> bool {
+        matches!(*self, Error::Http(_) | Error::Io(_)) || self.is_websocket_io()
+    }
+
+    /// Returns the error code if a Slack Web API method returned one.
+    pub fn code(&self) -> Option<&str> {
+        match *self {
+            Error::Slack { ref code, .. } => Some(code),
+            _ => None,
+        }
+    }
+
+    /// Returns the Slack error code if the token was rejected, e.g.
+    /// `invalid_auth` or `account_inactive`.
+    ///
+    /// Retrying with the same token after such an error is pointless.
+    pub fn auth_failure(&self) -> Option<&str> {
+        const AUTH_CODES: &[&str] = &[
+            "not_authed",
+            "invalid_auth",
+            "account_inactive",
+            "token_revoked",
+        ];
+        self.code().filter(|code| AUTH_CODES.contains(code))
+    }
+}
+
 impl From<api::requests::Error> for Error {
     fn from(err: api::requests::Error) -> Error {
         Error::Http(err)
@@ -65,7 +124,7 @@ impl From<::serde_json::Error> for Error {
 
 impl From<io::Error> for Error {
     fn from(err: io::Error) -> Error {
-        Error::Internal(format!("{:?}", err))
+        Error::Io(err)
     }
 }
 
@@ -75,12 +134,46 @@ impl From<FromUtf8Error> for Error {
     }
 }
 
-impl From<api::rtm::StartError<api::requests::Error>> for Error {
-    fn from(err: api::rtm::StartError<api::requests::Error>) -> Error {
-        Error::Api(format!("rtm::StartError: {}", err))
+/// Builds `Error::Slack` from an upstream error's `"code: description"` text.
+fn slack_error(method: &str, description: &str) -> Error {
+    let code = description.split(':').next().unwrap_or_default();
+    Error::Slack {
+        method: method.into(),
+        code: if code.is_empty() {
+            "unknown_error"
+        } else {
+            code
+        }
+        .into(),
     }
 }
 
+macro_rules! from_slack_api_error {
+    ($($module:ident::$error:ident => $method:expr,)*) => {$(
+        impl<E: error::Error> From<api::$module::$error<E>> for Error
+        where
+            Error: From<E>,
+        {
+            fn from(err: api::$module::$error<E>) -> Error {
+                match err {
+                    api::$module::$error::Client(err) => err.into(),
+                    api::$module::$error::MalformedResponse(_, err) => Error::Json(err),
+                    err => slack_error($method, &err.to_string()),
+                }
+            }
+        }
+    )*};
+}
+
+from_slack_api_error! {
+    rtm::StartError => "rtm.start",
+    reactions::AddError => "reactions.add",
+    reactions::RemoveError => "reactions.remove",
+    chat::PostMessageError => "chat.postMessage",
+    search::MessagesError => "search.messages",
+    files::ListError => "files.list",
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
@@ -89,7 +182,13 @@ impl fmt::Display for Error {
             Error::Utf8(ref e) => write!(f, "Utf8 decode Error: {}", e),
             Error::Url(ref e) => write!(f, "Url Error: {}", e),
             Error::Json(ref e) => write!(f, "Json Error: {}", e),
+            Error::Io(ref e) => write!(f, "IO Error: {}", e),
+            Error::MalformedAck(ref e) => write!(f, "Malformed message ack: {}", e),
             Error::Api(ref st) => write!(f, "Slack Api Error: {}", st),
+            Error::Slack {
+                ref method,
+                ref code,
+            } => write!(f, "Slack Api Error: {}: {}", method, code),
             Error::Internal(ref st) => write!(f, "Internal Error: {}", st),
         }
     }
@@ -103,7 +202,104 @@ impl error::Error for Error {
             Error::Utf8(ref e) => Some(e),
             Error::Url(ref e) => Some(e),
             Error::Json(ref e) => Some(e),
-            Error::Api(_) | Error::Internal(_) => None,
+            Error::Io(ref e) => Some(e),
+            Error::MalformedAck(ref e) => Some(e),
+            Error::Api(_) | Error::Slack { .. } | Error::Internal(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as StdError;
+
+    #[test]
+    fn source_is_wrapped_error() {
+        let err: Error = io::Error::new(io::ErrorKind::TimedOut, "timed out").into();
+        let source = err.source().unwrap();
+        assert_eq!(
+            source.downcast_ref::<io::Error>().unwrap().kind(),
+            io::ErrorKind::TimedOut
+        );
+
+        let err: Error = serde_json::from_str::<u32>("nope").unwrap_err().into();
+        assert!(err.source().unwrap().is::<serde_json::Error>());
+
+        let err: Error = ::tungstenite::Error::ConnectionClosed.into();
+        assert!(err.source().unwrap().is::<::tungstenite::Error>());
+    }
+
+    #[test]
+    fn no_source_for_string_errors() {
+        assert!(Error::Api("invalid_auth".into()).source().is_none());
+        assert!(Error::Internal("rx disconnected".into()).source().is_none());
+    }
+
+    #[test]
+    fn auth_failure_code() {
+        let err: Error = api::rtm::StartError::<api::requests::Error>::AccountInactive.into();
+        assert_eq!(err.auth_failure(), Some("account_inactive"));
+        let err = Error::Slack {
+            method: "chat.postMessage".into(),
+            code: "token_revoked".into(),
+        };
+        assert_eq!(err.auth_failure(), Some("token_revoked"));
+        let err = Error::Slack {
+            method: "chat.postMessage".into(),
+            code: "channel_not_found".into(),
+        };
+        assert_eq!(err.auth_failure(), None);
+        let err = Error::Api("chat.postMessage: token_revoked".into());
+        assert_eq!(err.auth_failure(), None);
+        assert_eq!(Error::Internal("invalid_auth".into()).auth_failure(), None);
+    }
+
+    #[test]
+    fn upstream_errors_keep_method_and_code() {
+        let err: Error = api::reactions::AddError::<io::Error>::AlreadyReacted.into();
+        assert_eq!(err.code(), Some("already_reacted"));
+        assert_eq!(
+            err.to_string(),
+            "Slack Api Error: reactions.add: already_reacted"
+        );
+        let err: Error =
+            api::chat::PostMessageError::<io::Error>::Unknown("rate_limited".into()).into();
+        assert_eq!(err.code(), Some("rate_limited"));
+        let err: Error =
+            api::search::MessagesError::<io::Error>::Client(io::ErrorKind::TimedOut.into()).into();
+        assert!(err.is_transient());
+    }
+
+    #[test]
+    fn transient_errors() {
+        let io = io::Error::new(io::ErrorKind::ConnectionRefused, "refused");
+        assert!(Error::Io(io).is_transient());
+        let err = Error::Slack {
+            method: "rtm.start".into(),
+            code: "invalid_auth".into(),
+        };
+        assert!(!err.is_transient());
+        assert!(!Error::Internal("rx disconnected".into()).is_transient());
+    }
+
+    #[test]
+    fn malformed_json_is_not_api_error() {
+        match crate::Event::from_json(r#"{"type": "hello""#) {
+            Err(Error::Json(e)) => assert!(e.is_eof()),
+            r => panic!("unexpected result: {:?}", r),
+        }
+
+        let client = api::mock::MockSender::new(&["<html>Bad Gateway</html>", r#"{"ok": false}"#]);
+        match api::call::<_, serde_json::Value>(&client, "auth.test", &[]) {
+            Err(Error::Json(e)) => assert!(e.is_syntax()),
+            r => panic!("unexpected result: {:?}", r),
+        }
+        match api::call::<_, serde_json::Value>(&client, "auth.test", &[]) {
+            Err(Error::Slack { method, code }) => {
+                assert_eq!((&method[..], &code[..]), ("auth.test", "unknown_error"))
+            }
+            r => panic!("unexpected result: {:?}", r),
         }
     }
 }
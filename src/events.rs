@@ -14,15 +14,21 @@
 // limitations under the License.
 //
 
+use std::convert::TryFrom;
+
 use crate::api::{
-    reactions, stars, Bot, Channel, File, FileComment, Message, MessagePinnedItem,
-    MessageUnpinnedItem, User,
+    stars, Bot, Channel, File, FileComment, Item, ItemFile, Message, Timestamp, User,
 };
+use crate::timestamp::{exact, exact_opt};
 
 /// Represents Slack [rtm event](https://api.slack.com/rtm) types.
+///
+/// Slack adds new event types over time, so this enum is non-exhaustive and
+/// events of an unrecognized type are decoded as `Event::Unknown`.
 #[derive(Clone, Debug, Deserialize)]
 #[serde(tag = "type")]
 #[serde(rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum Event {
     /// Represents the slack [`hello`](https://api.slack.com/events/hello) event.
     Hello,
@@ -34,7 +40,11 @@ pub enum Event {
     UserTyping { channel: String, user: String },
     /// Represents the slack
     /// [`channel_marked`](https://api.slack.com/events/channel_marked) event.
-    ChannelMarked { channel: String, ts: String },
+    ChannelMarked {
+        channel: String,
+        #[serde(deserialize_with = "exact")]
+        ts: Timestamp,
+    },
     /// Represents the slack
     /// [`channel_created`](https://api.slack.com/events/channel_created) event.
     ChannelCreated { channel: Box<Channel> },
@@ -55,13 +65,34 @@ pub enum Event {
     ChannelArchive { channel: String, user: String },
     /// Represents the slack
     /// [`channel_unarchive`](https://api.slack.com/events/channel_unarchive) event.
+    #[serde(rename = "channel_unarchive")]
     ChannelUnArchive { channel: String, user: String },
     /// Represents the slack
+    /// [`member_joined_channel`](https://api.slack.com/events/member_joined_channel) event.
+    MemberJoinedChannel {
+        user: String,
+        channel: String,
+        channel_type: String,
+        team: String,
+        inviter: Option<String>,
+    },
+    /// Represents the slack
+    /// [`member_left_channel`](https://api.slack.com/events/member_left_channel) event.
+    MemberLeftChannel {
+        user: String,
+        channel: String,
+        channel_type: String,
+        team: String,
+    },
+    /// Represents the slack
     /// [`channel_history_changed`](https://api.slack.com/events/channel_history_changed) event.
     ChannelHistoryChanged {
-        latest: String,
-        ts: String,
-        event_ts: String,
+        #[serde(deserialize_with = "exact")]
+        latest: Timestamp,
+        #[serde(deserialize_with = "exact")]
+        ts: Timestamp,
+        #[serde(deserialize_with = "exact")]
+        event_ts: Timestamp,
     },
     /// Represents the slack
     /// [`im_created`](https://api.slack.com/events/im_created) event.
@@ -74,14 +105,21 @@ pub enum Event {
     ImClose { user: String, channel: String },
     /// Represents the slack [`im_marked`](https://api.slack.com/events/im_marked)
     /// event.
-    ImMarked { channel: String, ts: String },
+    ImMarked {
+        channel: String,
+        #[serde(deserialize_with = "exact")]
+        ts: Timestamp,
+    },
     /// Represents the slack
     /// [`im_history_changed`](https://api.slack.com/events/im_history_changed)
     /// event.
     ImHistoryChanged {
-        latest: String,
-        ts: String,
-        event_ts: String,
+        #[serde(deserialize_with = "exact")]
+        latest: Timestamp,
+        #[serde(deserialize_with = "exact")]
+        ts: Timestamp,
+        #[serde(deserialize_with = "exact")]
+        event_ts: Timestamp,
     },
     /// Represents the slack [`goodbye`](https://api.slack.com/events/goodbye) event.
     Goodbye,
@@ -102,41 +140,54 @@ pub enum Event {
     GroupArchive { channel: String },
     /// Represents the slack
     /// [`group_unarchive`](https://api.slack.com/events/group_unarchive) event.
+    #[serde(rename = "group_unarchive")]
     GroupUnArchive { channel: String },
     /// Represents the slack
     /// [`group_rename`](https://api.slack.com/events/group_rename) event.
     GroupRename { channel: Box<Channel> },
     /// Represents the slack
     /// [`group_marked`](https://api.slack.com/events/group_marked) event.
-    GroupMarked { channel: String, ts: String },
+    GroupMarked {
+        channel: String,
+        #[serde(deserialize_with = "exact")]
+        ts: Timestamp,
+    },
     /// Represents the slack
     /// [`group_history_changed`](https://api.slack.com/events/group_history_changed) event.
     GroupHistoryChanged {
-        latest: String,
-        ts: String,
-        event_ts: String,
+        #[serde(deserialize_with = "exact")]
+        latest: Timestamp,
+        #[serde(deserialize_with = "exact")]
+        ts: Timestamp,
+        #[serde(deserialize_with = "exact")]
+        event_ts: Timestamp,
     },
     /// Represents the slack
     /// [`file_created`](https://api.slack.com/events/file_created) event.
-    FileCreated { file: Box<File> },
+    FileCreated(FileEvent),
     /// Represents the slack
     /// [`file_shared`](https://api.slack.com/events/file_shared) event.
-    FileShared { file: Box<File> },
+    FileShared(FileEvent),
     /// Represents the slack
     /// [`file_unshared`](https://api.slack.com/events/file_unshared) event.
-    FileUnShared { file: Box<File> },
+    #[serde(rename = "file_unshared")]
+    FileUnShared(FileEvent),
     /// Represents the slack
     /// [`file_public`](https://api.slack.com/events/file_public) event.
-    FilePublic { file: Box<File> },
+    FilePublic(FileEvent),
     /// Represents the slack
     /// [`file_private`](https://api.slack.com/events/file_private) event.
-    FilePrivate { file: String },
+    FilePrivate(FileEvent),
     /// Represents the slack
     /// [`file_change`](https://api.slack.com/events/file_change) event.
-    FileChange { file: Box<File> },
+    FileChange(FileEvent),
     /// Represents the slack
     /// [`file_deleted`](https://api.slack.com/events/file_deleted) event.
-    FileDeleted { file_id: String, event_ts: String },
+    FileDeleted {
+        file_id: String,
+        #[serde(deserialize_with = "exact")]
+        event_ts: Timestamp,
+    },
     /// Represents the slack
     /// [`file_comment_added`](https://api.slack.com/events/file_comment_added)
     /// event.
@@ -160,17 +211,19 @@ pub enum Event {
     PinAdded {
         user: String,
         channel_id: String,
-        item: Box<MessagePinnedItem>,
-        event_ts: String,
+        item: Box<Item>,
+        #[serde(deserialize_with = "exact")]
+        event_ts: Timestamp,
     },
     /// Represents the slack
     /// [`pin_removed`](https://api.slack.com/events/pin_removed) event.
     PinRemoved {
         user: String,
         channel_id: String,
-        item: Box<MessageUnpinnedItem>,
+        item: Box<Item>,
         has_pins: bool,
-        event_ts: String,
+        #[serde(deserialize_with = "exact")]
+        event_ts: Timestamp,
     },
     /// Represents the slack
     /// [`presence_change`](https://api.slack.com/events/presence_change) event.
@@ -196,39 +249,51 @@ pub enum Event {
     StarAdded {
         user: String,
         item: Box<stars::ListResponseItem>,
-        event_ts: String,
+        #[serde(deserialize_with = "exact")]
+        event_ts: Timestamp,
     },
     /// Represents the slack
     /// [`star_removed`](https://api.slack.com/events/star_removed) event.
     StarRemoved {
         user: String,
         item: Box<stars::ListResponseItem>,
-        event_ts: String,
+        #[serde(deserialize_with = "exact")]
+        event_ts: Timestamp,
     },
     /// Represents the slack
     /// [`reaction_added`](https://api.slack.com/events/reaction_added) event.
     ReactionAdded {
         user: String,
         reaction: String,
-        item: Box<reactions::ListResponseItem>,
-        item_user: String,
-        event_ts: String,
+        item: Box<Item>,
+        /// Author of the item, absent e.g. for some bot messages
+        item_user: Option<String>,
+        #[serde(deserialize_with = "exact")]
+        event_ts: Timestamp,
     },
     /// Represents the slack
     /// [`reaction_removed`](https://api.slack.com/events/reaction_removed) event.
     ReactionRemoved {
         user: String,
         reaction: String,
-        item: Box<reactions::ListResponseItem>,
-        item_user: String,
-        event_ts: String,
+        item: Box<Item>,
+        /// Author of the item, absent e.g. for some bot messages
+        item_user: Option<String>,
+        #[serde(deserialize_with = "exact")]
+        event_ts: Timestamp,
     },
     /// Represents the slack
     /// [`emoji_changed`](https://api.slack.com/events/emoji_changed) event.
-    EmojiChanged { event_ts: String },
+    EmojiChanged {
+        #[serde(deserialize_with = "exact")]
+        event_ts: Timestamp,
+    },
     /// Represents the slack
     /// [`commands_changed`](https://api.slack.com/events/commands_changed) event.
-    CommandsChanged { event_ts: String },
+    CommandsChanged {
+        #[serde(deserialize_with = "exact")]
+        event_ts: Timestamp,
+    },
     /// Represents the slack
     /// [`team_plan_change`](https://api.slack.com/events/team_plan_change) event.
     TeamPlanChange { plan: String },
@@ -246,7 +311,8 @@ pub enum Event {
     /// [`email_domain_changed`](https://api.slack.com/events/email_domain_changed) event.
     EmailDomainChanged {
         email_domain: String,
-        event_ts: String,
+        #[serde(deserialize_with = "exact")]
+        event_ts: Timestamp,
     },
     /// Represents the slack [`bot_added`](https://api.slack.com/events/bot_added)
     /// event.
@@ -274,7 +340,8 @@ pub enum Event {
         avatar_image: Option<String>,
         channel: Option<String>,
         content: Option<String>,
-        event_ts: Option<String>,
+        #[serde(default, deserialize_with = "exact_opt")]
+        event_ts: Option<Timestamp>,
         #[serde(rename = "imageUri")]
         image_uri: Option<String>,
         is_shared: Option<bool>,
@@ -284,9 +351,71 @@ pub enum Event {
         #[serde(rename = "ssbFilename")]
         ssb_filename: Option<String>,
         subtitle: Option<String>,
-        thread_ts: Option<String>,
+        #[serde(default, deserialize_with = "exact_opt")]
+        thread_ts: Option<Timestamp>,
         title: Option<String>,
     },
+    /// An event with a `type` this crate does not know about.
+    ///
+    /// Also used for `message` events whose `subtype` isn't one of the
+    /// `Message` variants, e.g. `channel_convert_to_private`; the subtype is
+    /// in `raw["subtype"]`.
+    #[serde(skip)]
+    Unknown {
+        /// The event's `type`
+        ty: String,
+        /// The complete event
+        raw: serde_json::Value,
+    },
+}
+
+/// A `file_*` event about a single file.
+///
+/// Slack used to send the full `file` object with these events, but now sends
+/// only its `file_id`, along with a `file` holding little more than the id;
+/// fetch the details with `api::files::info`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "RawFileEvent")]
+pub struct FileEvent {
+    pub file_id: String,
+    pub file: Option<Box<File>>,
+    pub user_id: Option<String>,
+    pub channel_id: Option<String>,
+    pub event_ts: Option<Timestamp>,
+}
+
+/// A `FileEvent` in either the legacy or the current shape
+#[derive(Deserialize)]
+struct RawFileEvent {
+    file_id: Option<String>,
+    file: Option<ItemFile>,
+    user_id: Option<String>,
+    channel_id: Option<String>,
+    #[serde(default, deserialize_with = "exact_opt")]
+    event_ts: Option<Timestamp>,
+}
+
+impl TryFrom<RawFileEvent> for FileEvent {
+    type Error = String;
+
+    fn try_from(raw: RawFileEvent) -> Result<FileEvent, String> {
+        let file = raw.file;
+        let file_id = raw
+            .file_id
+            .or_else(|| file.as_ref().and_then(|f| f.id()).map(String::from))
+            .ok_or_else(|| "missing field `file_id`".to_string())?;
+        let file = match file {
+            Some(ItemFile::File(file)) => Some(file),
+            _ => None,
+        };
+        Ok(FileEvent {
+            file_id,
+            file,
+            user_id: raw.user_id,
+            channel_id: raw.channel_id,
+            event_ts: raw.event_ts,
+        })
+    }
 }
 
 /// Represents a confirmation of a message sent
@@ -295,7 +424,8 @@ pub struct MessageSent {
     pub ok: bool,
     pub reply_to: isize,
     pub text: String,
-    pub ts: String,
+    #[serde(deserialize_with = "exact")]
+    pub ts: Timestamp,
 }
 
 /// Represents an error sending a message
@@ -303,11 +433,13 @@ pub struct MessageSent {
 pub struct MessageError {
     pub ok: bool,
     pub reply_to: isize,
+    /// Left at its default (code `0`, empty `msg`) when Slack omits the details
+    #[serde(default)]
     pub error: MessageErrorDetail,
 }
 
 /// Details of an error sending a message
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct MessageErrorDetail {
     pub code: isize,
     pub msg: String,
@@ -316,7 +448,11 @@ pub struct MessageErrorDetail {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::api::{Message, MessageStandard};
+    use crate::api::{
+        Message, MessageBotMessage, MessageMessageChanged, MessageMessageReplied, MessageStandard,
+        MessageThreadBroadcast,
+    };
+    use crate::MessageExt;
 
     #[test]
     fn decode_short_standard_message() {
@@ -364,7 +500,7 @@ mod tests {
                 reply_to, ts, text, ..
             }) => {
                 assert_eq!(reply_to, 1);
-                assert_eq!(ts, "1234567890.218332");
+                assert_eq!(ts.to_string(), "1234567890.218332");
                 assert_eq!(text, "Hello world");
             }
             _ => panic!("Event decoded into incorrect variant."),
@@ -398,6 +534,96 @@ mod tests {
         }
     }
 
+    #[test]
+    fn decode_sent_not_ok_without_error() {
+        let event: Event = Event::from_json(r#"{"ok": false, "reply_to": 7}"#).unwrap();
+        match event {
+            Event::MessageError(MessageError {
+                ok,
+                reply_to,
+                error,
+                ..
+            }) => {
+                assert!(!ok);
+                assert_eq!(reply_to, 7);
+                assert_eq!(error.code, 0);
+                assert_eq!(error.msg, "");
+            }
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+    }
+
+    #[test]
+    fn malformed_ack_is_distinct_error() {
+        match Event::from_json(r#"{"ok": true, "reply_to": 7}"#) {
+            Err(crate::Error::MalformedAck(_)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        match Event::from_json(r#"{"ok": false, "reply_to": "seven"}"#) {
+            Err(crate::Error::MalformedAck(_)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn decode_legacy_file_shared() {
+        let event = Event::from_json(
+            r#"{
+            "type": "file_shared",
+            "file": {"id": "F2147483862", "name": "report.pdf", "mimetype": "application/pdf"}
+        }"#,
+        )
+        .unwrap();
+        match event {
+            Event::FileShared(FileEvent { file_id, file, .. }) => {
+                assert_eq!(file_id, "F2147483862");
+                assert_eq!(file.unwrap().name.as_deref(), Some("report.pdf"));
+            }
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+    }
+
+    #[test]
+    fn decode_file_shared_with_file_id() {
+        let event = Event::from_json(
+            r#"{
+            "type": "file_shared",
+            "channel_id": "C024BE7LR",
+            "file_id": "F2147483862",
+            "user_id": "U061F7AUR",
+            "file": {"id": "F2147483862"},
+            "event_ts": "1433831727.000001"
+        }"#,
+        )
+        .unwrap();
+        match event {
+            Event::FileShared(FileEvent {
+                file_id,
+                channel_id,
+                user_id,
+                ..
+            }) => {
+                assert_eq!(file_id, "F2147483862");
+                assert_eq!(channel_id.as_deref(), Some("C024BE7LR"));
+                assert_eq!(user_id.as_deref(), Some("U061F7AUR"));
+            }
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+    }
+
+    #[test]
+    fn decode_file_private_with_id_only() {
+        let event = Event::from_json(r#"{"type": "file_private", "file": "F2147483862"}"#).unwrap();
+        match event {
+            Event::FilePrivate(FileEvent { file_id, file, .. }) => {
+                assert_eq!(file_id, "F2147483862");
+                assert!(file.is_none());
+            }
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+        assert!(Event::from_json(r#"{"type": "file_created"}"#).is_err());
+    }
+
     #[test]
     fn decode_presence_change_event() {
         let event: Event = Event::from_json(
@@ -450,6 +676,389 @@ mod tests {
         }
     }
 
+    #[test]
+    fn decode_member_joined_channel_event() {
+        let event: Event = Event::from_json(
+            r#"{
+            "type": "member_joined_channel",
+            "user": "W06GH7XHN",
+            "channel": "C0698JE0H",
+            "channel_type": "C",
+            "team": "T024BE7LD",
+            "inviter": "U123456789"
+        }"#,
+        )
+        .unwrap();
+        match event {
+            Event::MemberJoinedChannel {
+                user,
+                channel,
+                channel_type,
+                team,
+                inviter,
+            } => {
+                assert_eq!(user, "W06GH7XHN");
+                assert_eq!(channel, "C0698JE0H");
+                assert_eq!(channel_type, "C");
+                assert_eq!(team, "T024BE7LD");
+                assert_eq!(inviter.unwrap(), "U123456789");
+            }
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+    }
+
+    #[test]
+    fn decode_member_left_channel_event() {
+        let event: Event = Event::from_json(
+            r#"{
+            "type": "member_left_channel",
+            "user": "W06GH7XHN",
+            "channel": "C0698JE0H",
+            "channel_type": "G",
+            "team": "T024BE7LD"
+        }"#,
+        )
+        .unwrap();
+        match event {
+            Event::MemberLeftChannel {
+                user,
+                channel,
+                channel_type,
+                ..
+            } => {
+                assert_eq!(user, "W06GH7XHN");
+                assert_eq!(channel, "C0698JE0H");
+                assert_eq!(channel_type, "G");
+            }
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+    }
+
+    #[test]
+    fn decode_reaction_added_event() {
+        let event: Event = Event::from_json(
+            r#"{
+            "type": "reaction_added",
+            "user": "U024BE7LH",
+            "reaction": "thumbsup",
+            "item_user": "U0G9QF9C6",
+            "item": {
+                "type": "message",
+                "channel": "C0G9QF9GZ",
+                "ts": "1360782400.498405"
+            },
+            "event_ts": "1360782804.083113"
+        }"#,
+        )
+        .unwrap();
+        match event {
+            Event::ReactionAdded {
+                item, item_user, ..
+            } => {
+                assert_eq!(item_user.as_deref(), Some("U0G9QF9C6"));
+                match *item {
+                    Item::Message { channel, .. } => assert_eq!(channel, "C0G9QF9GZ"),
+                    item => panic!("unexpected item: {:?}", item),
+                }
+            }
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+    }
+
+    #[test]
+    fn decode_pin_added_file() {
+        let event: Event = Event::from_json(
+            r#"{
+            "type": "pin_added",
+            "user": "U024BE7LH",
+            "channel_id": "C02ELGNBH",
+            "item": {
+                "type": "file",
+                "created": 1360782804,
+                "created_by": "U024BE7LH",
+                "file": {
+                    "id": "F0HS27V1Z",
+                    "name": "deploy.log",
+                    "created": 1360782800,
+                    "timestamp": 1360782800
+                }
+            },
+            "event_ts": "1360782804.083113"
+        }"#,
+        )
+        .unwrap();
+        match event {
+            Event::PinAdded {
+                item, channel_id, ..
+            } => {
+                assert_eq!(channel_id, "C02ELGNBH");
+                match *item {
+                    Item::File {
+                        file: ItemFile::File(file),
+                    } => assert_eq!(file.name.as_deref(), Some("deploy.log")),
+                    item => panic!("unexpected item: {:?}", item),
+                }
+            }
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+    }
+
+    #[test]
+    fn decode_pin_removed_message() {
+        let event: Event = Event::from_json(
+            r#"{
+            "type": "pin_removed",
+            "user": "U024BE7LH",
+            "channel_id": "C02ELGNBH",
+            "item": {
+                "type": "message",
+                "channel": "C02ELGNBH",
+                "message": {
+                    "type": "message",
+                    "user": "U024BE7LH",
+                    "text": "release notes",
+                    "ts": "1360782400.498405"
+                }
+            },
+            "has_pins": false,
+            "event_ts": "1360782845.083113"
+        }"#,
+        )
+        .unwrap();
+        match event {
+            Event::PinRemoved { item, has_pins, .. } => {
+                assert!(!has_pins);
+                match *item {
+                    Item::Message {
+                        channel,
+                        message: Some(message),
+                        ..
+                    } => {
+                        assert_eq!(channel, "C02ELGNBH");
+                        assert_eq!(message.text(), Some("release notes"));
+                    }
+                    item => panic!("unexpected item: {:?}", item),
+                }
+            }
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+    }
+
+    #[test]
+    fn decode_reaction_removed_without_item_user() {
+        let event: Event = Event::from_json(
+            r#"{
+            "type": "reaction_removed",
+            "user": "U024BE7LH",
+            "reaction": "thumbsup",
+            "item": {
+                "type": "file_comment",
+                "file_comment": "Fc0HS2KBEZ",
+                "file": "F0HS27V1Z"
+            },
+            "event_ts": "1360782804.083113"
+        }"#,
+        )
+        .unwrap();
+        match event {
+            Event::ReactionRemoved {
+                item, item_user, ..
+            } => {
+                assert!(item_user.is_none());
+                match *item {
+                    Item::FileComment {
+                        file, file_comment, ..
+                    } => {
+                        assert_eq!(file.id(), Some("F0HS27V1Z"));
+                        assert_eq!(file_comment.as_deref(), Some("Fc0HS2KBEZ"));
+                    }
+                    item => panic!("unexpected item: {:?}", item),
+                }
+            }
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+    }
+
+    #[test]
+    fn decode_message_replied() {
+        let event: Event = Event::from_json(
+            r#"{
+            "type": "message",
+            "subtype": "message_replied",
+            "hidden": true,
+            "channel": "C061EG9SL",
+            "event_ts": "1483037604.017506",
+            "ts": "1483037604.017506",
+            "message": {
+                "type": "message",
+                "user": "U061F7AUR",
+                "text": "Was there was there was there what was there was there what was there was there there was there.",
+                "thread_ts": "1482960137.003543",
+                "reply_count": 1,
+                "replies": [
+                    {"user": "U061F7AUR", "ts": "1483037603.017503"}
+                ],
+                "ts": "1482960137.003543"
+            }
+        }"#,
+        )
+        .unwrap();
+        match event {
+            Event::Message(message) => match *message {
+                Message::MessageReplied(MessageMessageReplied {
+                    hidden, message, ..
+                }) => {
+                    assert_eq!(hidden, Some(true));
+                    let parent = message.unwrap();
+                    assert_eq!(parent.reply_count, Some(1));
+                    assert_eq!(
+                        parent.replies.unwrap()[0].ts.unwrap().to_string(),
+                        "1483037603.017503"
+                    );
+                }
+                _ => panic!("Message decoded into incorrect variant."),
+            },
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+    }
+
+    #[test]
+    fn decode_thread_broadcast_message() {
+        let event: Event = Event::from_json(
+            r#"{
+            "type": "message",
+            "subtype": "thread_broadcast",
+            "user": "U061F7AUR",
+            "ts": "1483051909.018632",
+            "thread_ts": "1483037603.017503",
+            "root": {
+                "text": "Who knows where the time goes?",
+                "user": "U061F7AUR",
+                "ts": "1483037603.017503",
+                "thread_ts": "1483037603.017503",
+                "reply_count": 1,
+                "replies": [{ "user": "U061F7AUR", "ts": "1483051909.018632" }]
+            }
+        }"#,
+        )
+        .unwrap();
+        match event {
+            Event::Message(message) => match *message {
+                Message::ThreadBroadcast(MessageThreadBroadcast {
+                    user,
+                    ts,
+                    thread_ts,
+                    root,
+                    ..
+                }) => {
+                    assert_eq!(user.unwrap(), "U061F7AUR");
+                    assert_eq!(ts.unwrap().to_string(), "1483051909.018632");
+                    assert_eq!(thread_ts.unwrap().to_string(), "1483037603.017503");
+                    let root = root.unwrap();
+                    assert_eq!(root.text.unwrap(), "Who knows where the time goes?");
+                    assert_eq!(root.reply_count, Some(1));
+                }
+                _ => panic!("Message decoded into incorrect variant."),
+            },
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+    }
+
+    #[test]
+    fn decode_unknown_event() {
+        let event: Event = Event::from_json(
+            r#"{
+            "type": "some_future_event",
+            "user": "U061F7AUR",
+            "event_ts": "1360782804.083113"
+        }"#,
+        )
+        .unwrap();
+        match event {
+            Event::Unknown { ty, raw } => {
+                assert_eq!(ty, "some_future_event");
+                assert_eq!(raw["user"], "U061F7AUR");
+            }
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+    }
+
+    #[test]
+    fn decode_channel_convert_subtypes() {
+        for subtype in &["channel_convert_to_private", "channel_convert_to_public"] {
+            let event = Event::from_json(&format!(
+                r#"{{
+                "type": "message",
+                "subtype": "{}",
+                "channel": "C0698JE0H",
+                "user": "U061F7AUR",
+                "text": "made this channel private",
+                "ts": "1565203203.000200"
+            }}"#,
+                subtype
+            ))
+            .unwrap();
+            match event {
+                Event::Unknown { ty, raw } => {
+                    assert_eq!(ty, "message");
+                    assert_eq!(raw["subtype"], *subtype);
+                    assert_eq!(raw["user"], "U061F7AUR");
+                    assert_eq!(raw["text"], "made this channel private");
+                    assert_eq!(raw["ts"], "1565203203.000200");
+                }
+                _ => panic!("Event decoded into incorrect variant."),
+            }
+        }
+    }
+
+    #[test]
+    fn event_timestamps_are_exact() {
+        let event = Event::from_json(
+            r#"{"type": "channel_marked", "channel": "C024BE91L", "ts": "1588859442.000249"}"#,
+        )
+        .unwrap();
+        match event {
+            Event::ChannelMarked { ts, .. } => assert_eq!(ts.to_string(), "1588859442.000249"),
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+        assert!(Event::from_json(
+            r#"{"type": "channel_marked", "channel": "C024BE91L", "ts": "soon"}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn malformed_known_event_is_error() {
+        assert!(Event::from_json(r#"{"type": "channel_marked", "ts": 12}"#).is_err());
+    }
+
+    #[test]
+    fn known_event_with_unknown_nested_item_is_error() {
+        let pin = r#"{
+            "type": "pin_added",
+            "user": "U024BE7LH",
+            "channel_id": "C02ELGNBH",
+            "item": {"type": "channel", "channel": "C02ELGNBH"},
+            "event_ts": "1360782804.083113"
+        }"#;
+        let reaction = r#"{
+            "type": "reaction_added",
+            "user": "U024BE7LH",
+            "reaction": "thumbsup",
+            "item": {"type": "bogus", "channel": "C0G9QF9GZ", "ts": "1360782400.498405"},
+            "event_ts": "1360782804.083113",
+            "ts": "1360782804.083113"
+        }"#;
+        for json in &[pin, reaction] {
+            match Event::from_json(json) {
+                Err(crate::Error::Json(e)) => {
+                    assert!(e.to_string().contains("unknown variant"), "{}", e)
+                }
+                r => panic!("unexpected result: {:?}", r),
+            }
+        }
+    }
+
     #[test]
     fn decode_extended_standard_message() {
         let event: Event = Event::from_json(
@@ -506,4 +1115,568 @@ mod tests {
             _ => panic!("Event decoded into incorrect variant."),
         }
     }
+
+    #[test]
+    fn decode_message_changed_keeps_bot_id() {
+        let event: Event = Event::from_json(
+            r#"{
+            "type": "message",
+            "subtype": "message_changed",
+            "hidden": true,
+            "channel": "C2147483705",
+            "ts": "1358878755.000001",
+            "message": {
+                "type": "message",
+                "bot_id": "B0AB12CDE",
+                "text": "Deploy finished",
+                "ts": "1358877455.000010",
+                "edited": {"user": "U2147483697", "ts": "1358878755.000001"}
+            },
+            "previous_message": {
+                "type": "message",
+                "bot_id": "B0AB12CDE",
+                "text": "Deploy started",
+                "ts": "1358877455.000010"
+            }
+        }"#,
+        )
+        .unwrap();
+        match event {
+            Event::Message(message) => match *message {
+                Message::MessageChanged(MessageMessageChanged {
+                    message,
+                    previous_message,
+                    ..
+                }) => {
+                    assert_eq!(message.unwrap().bot_id.unwrap(), "B0AB12CDE");
+                    assert_eq!(previous_message.unwrap().bot_id.unwrap(), "B0AB12CDE");
+                }
+                _ => panic!("Message decoded into incorrect variant."),
+            },
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+    }
+
+    #[test]
+    fn decode_standard_message_with_bot_id() {
+        let event: Event = Event::from_json(
+            r#"{
+            "type": "message",
+            "bot_id": "B0AB12CDE",
+            "text": "Deploy finished",
+            "channel": "C2147483705",
+            "ts": "1358877455.000010"
+        }"#,
+        )
+        .unwrap();
+        match event {
+            Event::Message(message) => match *message {
+                Message::Standard(MessageStandard { bot_id, .. }) => {
+                    assert_eq!(bot_id.unwrap(), "B0AB12CDE");
+                }
+                _ => panic!("Message decoded into incorrect variant."),
+            },
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+    }
+
+    #[test]
+    fn decode_bot_message_attachments() {
+        let event: Event = Event::from_json(
+            r##"{
+            "type": "message",
+            "subtype": "bot_message",
+            "bot_id": "B0GH1TH0B",
+            "username": "GitHub",
+            "icons": {"image_48": "https://example.com/github_48.png"},
+            "text": "",
+            "channel": "C2147483705",
+            "ts": "1583931425.000100",
+            "attachments": [
+                {
+                    "id": 1,
+                    "color": "24292f",
+                    "fallback": "[slack-rs] Pull request opened",
+                    "pretext": "Pull request opened by octocat",
+                    "title": "#42 Decode bot attachments",
+                    "title_link": "https://github.com/slack-rs/slack-rs/pull/42",
+                    "text": "Keeps rich content on bot messages",
+                    "fields": [{"title": "Reviewers", "value": "none", "short": true}],
+                    "footer": "GitHub",
+                    "ts": 1583931425,
+                    "mrkdwn_in": ["text"]
+                }
+            ]
+        }"##,
+        )
+        .unwrap();
+        match event {
+            Event::Message(message) => match *message {
+                Message::BotMessage(MessageBotMessage {
+                    attachments,
+                    bot_id,
+                    ..
+                }) => {
+                    assert_eq!(bot_id.unwrap(), "B0GH1TH0B");
+                    let attachment = &attachments.unwrap()[0];
+                    assert_eq!(
+                        attachment.title.as_ref().unwrap(),
+                        "#42 Decode bot attachments"
+                    );
+                    let fields = attachment.fields.as_ref().unwrap();
+                    assert_eq!(fields[0].value.as_ref().unwrap(), "none");
+                    assert_eq!(attachment.ts.unwrap().to_string(), "1583931425.000000");
+                }
+                _ => panic!("Message decoded into incorrect variant."),
+            },
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+    }
+
+    #[test]
+    fn decode_standard_message_with_files() {
+        // `MessageStandard` has no `files` field yet, but the message must
+        // still decode rather than fail on the unknown keys
+        let event: Event = Event::from_json(
+            r#"{
+            "type": "message",
+            "text": "here's the report",
+            "files": [
+                {
+                    "id": "F0123ABCD",
+                    "created": 1583931425,
+                    "timestamp": 1583931425,
+                    "name": "report.pdf",
+                    "title": "report.pdf",
+                    "mimetype": "application/pdf",
+                    "filetype": "pdf",
+                    "user": "U061F7AUR",
+                    "size": 1024,
+                    "url_private": "https://files.slack.com/files-pri/T1-F0123ABCD/report.pdf"
+                }
+            ],
+            "upload": true,
+            "user": "U061F7AUR",
+            "display_as_bot": false,
+            "channel": "C061EG9SL",
+            "ts": "1583931425.000200"
+        }"#,
+        )
+        .unwrap();
+        match event {
+            Event::Message(message) => match *message {
+                Message::Standard(MessageStandard { text, user, .. }) => {
+                    assert_eq!(text.unwrap(), "here's the report");
+                    assert_eq!(user.unwrap(), "U061F7AUR");
+                }
+                _ => panic!("Message decoded into incorrect variant."),
+            },
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+    }
+
+    #[test]
+    fn decode_star_added_channel() {
+        let event = Event::from_json(
+            r#"{
+            "type": "star_added",
+            "user": "U024BE7LH",
+            "item": {"type": "channel", "channel": "C2147483705", "date_create": 1360782400},
+            "event_ts": "1360782804.083113"
+        }"#,
+        )
+        .unwrap();
+        match event {
+            Event::StarAdded { item, .. } => match *item {
+                stars::ListResponseItem::Channel(item) => assert_eq!(item.channel, "C2147483705"),
+                _ => panic!("Item decoded into incorrect variant."),
+            },
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+    }
+
+    #[test]
+    fn decode_star_removed_group_and_im() {
+        let event = Event::from_json(
+            r#"{
+            "type": "star_removed",
+            "user": "U024BE7LH",
+            "item": {"type": "group", "group": "G024BE91L"},
+            "event_ts": "1360782804.083113"
+        }"#,
+        )
+        .unwrap();
+        match event {
+            Event::StarRemoved { item, .. } => match *item {
+                stars::ListResponseItem::Group(item) => assert_eq!(item.group, "G024BE91L"),
+                _ => panic!("Item decoded into incorrect variant."),
+            },
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+
+        let event = Event::from_json(
+            r#"{
+            "type": "star_removed",
+            "user": "U024BE7LH",
+            "item": {"type": "im", "channel": "D024BFF1M"},
+            "event_ts": "1360782804.083113"
+        }"#,
+        )
+        .unwrap();
+        match event {
+            Event::StarRemoved { item, .. } => match *item {
+                stars::ListResponseItem::Im(item) => assert_eq!(item.channel, "D024BFF1M"),
+                _ => panic!("Item decoded into incorrect variant."),
+            },
+            _ => panic!("Event decoded into incorrect variant."),
+        }
+    }
+
+    /// The name of `event`'s variant. Matching exhaustively makes a new
+    /// variant fail to compile here until it gets a sample below.
+    fn variant(event: &Event) -> &'static str {
+        match *event {
+            Event::Hello => "Hello",
+            Event::Message(_) => "Message",
+            Event::UserTyping { .. } => "UserTyping",
+            Event::ChannelMarked { .. } => "ChannelMarked",
+            Event::ChannelCreated { .. } => "ChannelCreated",
+            Event::ChannelJoined { .. } => "ChannelJoined",
+            Event::ChannelLeft { .. } => "ChannelLeft",
+            Event::ChannelDeleted { .. } => "ChannelDeleted",
+            Event::ChannelRename { .. } => "ChannelRename",
+            Event::ChannelArchive { .. } => "ChannelArchive",
+            Event::ChannelUnArchive { .. } => "ChannelUnArchive",
+            Event::MemberJoinedChannel { .. } => "MemberJoinedChannel",
+            Event::MemberLeftChannel { .. } => "MemberLeftChannel",
+            Event::ChannelHistoryChanged { .. } => "ChannelHistoryChanged",
+            Event::ImCreated { .. } => "ImCreated",
+            Event::ImOpen { .. } => "ImOpen",
+            Event::ImClose { .. } => "ImClose",
+            Event::ImMarked { .. } => "ImMarked",
+            Event::ImHistoryChanged { .. } => "ImHistoryChanged",
+            Event::Goodbye => "Goodbye",
+            Event::GroupJoined { .. } => "GroupJoined",
+            Event::GroupLeft { .. } => "GroupLeft",
+            Event::GroupOpen { .. } => "GroupOpen",
+            Event::GroupClose { .. } => "GroupClose",
+            Event::GroupArchive { .. } => "GroupArchive",
+            Event::GroupUnArchive { .. } => "GroupUnArchive",
+            Event::GroupRename { .. } => "GroupRename",
+            Event::GroupMarked { .. } => "GroupMarked",
+            Event::GroupHistoryChanged { .. } => "GroupHistoryChanged",
+            Event::FileCreated(_) => "FileCreated",
+            Event::FileShared(_) => "FileShared",
+            Event::FileUnShared(_) => "FileUnShared",
+            Event::FilePublic(_) => "FilePublic",
+            Event::FilePrivate(_) => "FilePrivate",
+            Event::FileChange(_) => "FileChange",
+            Event::FileDeleted { .. } => "FileDeleted",
+            Event::FileCommentAdded { .. } => "FileCommentAdded",
+            Event::FileCommentEdited { .. } => "FileCommentEdited",
+            Event::FileCommentDeleted { .. } => "FileCommentDeleted",
+            Event::PinAdded { .. } => "PinAdded",
+            Event::PinRemoved { .. } => "PinRemoved",
+            Event::PresenceChange { .. } => "PresenceChange",
+            Event::ManualPresenceChange { .. } => "ManualPresenceChange",
+            Event::PrefChange { .. } => "PrefChange",
+            Event::UserChange { .. } => "UserChange",
+            Event::TeamJoin { .. } => "TeamJoin",
+            Event::StarAdded { .. } => "StarAdded",
+            Event::StarRemoved { .. } => "StarRemoved",
+            Event::ReactionAdded { .. } => "ReactionAdded",
+            Event::ReactionRemoved { .. } => "ReactionRemoved",
+            Event::EmojiChanged { .. } => "EmojiChanged",
+            Event::CommandsChanged { .. } => "CommandsChanged",
+            Event::TeamPlanChange { .. } => "TeamPlanChange",
+            Event::TeamPrefChange { .. } => "TeamPrefChange",
+            Event::TeamRename { .. } => "TeamRename",
+            Event::TeamDomainChange { .. } => "TeamDomainChange",
+            Event::EmailDomainChanged { .. } => "EmailDomainChanged",
+            Event::BotAdded { .. } => "BotAdded",
+            Event::BotChanged { .. } => "BotChanged",
+            Event::AccountsChanged => "AccountsChanged",
+            Event::TeamMigrationStarted => "TeamMigrationStarted",
+            Event::ReconnectUrl { .. } => "ReconnectUrl",
+            Event::MessageSent(_) => "MessageSent",
+            Event::MessageError(_) => "MessageError",
+            Event::DesktopNotification { .. } => "DesktopNotification",
+            Event::Unknown { .. } => "Unknown",
+        }
+    }
+
+    /// A sample frame for each variant, after the examples in Slack's docs
+    const SAMPLES: &[(&str, &str)] = &[
+        ("Hello", r#"{"type": "hello"}"#),
+        (
+            "Message",
+            r#"{"type": "message", "channel": "C2147483705", "user": "U2147483697", "text": "Hello world", "ts": "1355517523.000005"}"#,
+        ),
+        (
+            "UserTyping",
+            r#"{"type": "user_typing", "channel": "C02ELGNBH", "user": "U024BE7LH"}"#,
+        ),
+        (
+            "ChannelMarked",
+            r#"{"type": "channel_marked", "channel": "C024BE91L", "ts": "1401383885.000061"}"#,
+        ),
+        (
+            "ChannelCreated",
+            r#"{"type": "channel_created", "channel": {"id": "C024BE91L", "name": "fun", "created": 1360782804, "creator": "U024BE7LH"}}"#,
+        ),
+        (
+            "ChannelJoined",
+            r#"{"type": "channel_joined", "channel": {"id": "C024BE91L", "name": "fun"}}"#,
+        ),
+        (
+            "ChannelLeft",
+            r#"{"type": "channel_left", "channel": "C024BE91L"}"#,
+        ),
+        (
+            "ChannelDeleted",
+            r#"{"type": "channel_deleted", "channel": "C024BE91L"}"#,
+        ),
+        (
+            "ChannelRename",
+            r#"{"type": "channel_rename", "channel": {"id": "C02ELGNBH", "name": "new_name", "created": 1360782804}}"#,
+        ),
+        (
+            "ChannelArchive",
+            r#"{"type": "channel_archive", "channel": "C024BE91L", "user": "U024BE7LH"}"#,
+        ),
+        (
+            "ChannelUnArchive",
+            r#"{"type": "channel_unarchive", "channel": "C024BE91L", "user": "U024BE7LH"}"#,
+        ),
+        (
+            "MemberJoinedChannel",
+            r#"{"type": "member_joined_channel", "user": "W06GH7XHN", "channel": "C0698JE0H", "channel_type": "C", "team": "T024BE7LD", "inviter": "U123456789"}"#,
+        ),
+        (
+            "MemberLeftChannel",
+            r#"{"type": "member_left_channel", "user": "W06GH7XHN", "channel": "C0698JE0H", "channel_type": "C", "team": "T024BE7LD"}"#,
+        ),
+        (
+            "ChannelHistoryChanged",
+            r#"{"type": "channel_history_changed", "latest": "1358877455.000010", "ts": "1361482916.000003", "event_ts": "1361482916.000004"}"#,
+        ),
+        (
+            "ImCreated",
+            r#"{"type": "im_created", "user": "U024BE7LH", "channel": {"id": "D024BE91L"}}"#,
+        ),
+        (
+            "ImOpen",
+            r#"{"type": "im_open", "user": "U024BE7LH", "channel": "D024BE91L"}"#,
+        ),
+        (
+            "ImClose",
+            r#"{"type": "im_close", "user": "U024BE7LH", "channel": "D024BE91L"}"#,
+        ),
+        (
+            "ImMarked",
+            r#"{"type": "im_marked", "channel": "D024BE91L", "ts": "1401383885.000061"}"#,
+        ),
+        (
+            "ImHistoryChanged",
+            r#"{"type": "im_history_changed", "latest": "1358877455.000010", "ts": "1361482916.000003", "event_ts": "1361482916.000004"}"#,
+        ),
+        ("Goodbye", r#"{"type": "goodbye"}"#),
+        (
+            "GroupJoined",
+            r#"{"type": "group_joined", "channel": {"id": "G024BE91L", "name": "secretplans"}}"#,
+        ),
+        (
+            "GroupLeft",
+            r#"{"type": "group_left", "channel": {"id": "G024BE91L"}}"#,
+        ),
+        (
+            "GroupOpen",
+            r#"{"type": "group_open", "user": "U024BE7LH", "channel": "G024BE91L"}"#,
+        ),
+        (
+            "GroupClose",
+            r#"{"type": "group_close", "user": "U024BE7LH", "channel": "G024BE91L"}"#,
+        ),
+        (
+            "GroupArchive",
+            r#"{"type": "group_archive", "channel": "G024BE91L"}"#,
+        ),
+        (
+            "GroupUnArchive",
+            r#"{"type": "group_unarchive", "channel": "G024BE91L"}"#,
+        ),
+        (
+            "GroupRename",
+            r#"{"type": "group_rename", "channel": {"id": "G02ELGNBH", "name": "new_name", "created": 1360782804}}"#,
+        ),
+        (
+            "GroupMarked",
+            r#"{"type": "group_marked", "channel": "G024BE91L", "ts": "1401383885.000061"}"#,
+        ),
+        (
+            "GroupHistoryChanged",
+            r#"{"type": "group_history_changed", "latest": "1358877455.000010", "ts": "1361482916.000003", "event_ts": "1361482916.000004"}"#,
+        ),
+        (
+            "FileCreated",
+            r#"{"type": "file_created", "file_id": "F2147483862", "file": {"id": "F2147483862"}}"#,
+        ),
+        (
+            "FileShared",
+            r#"{"type": "file_shared", "file_id": "F2147483862", "file": {"id": "F2147483862"}}"#,
+        ),
+        (
+            "FileUnShared",
+            r#"{"type": "file_unshared", "file_id": "F2147483862", "file": {"id": "F2147483862"}}"#,
+        ),
+        (
+            "FilePublic",
+            r#"{"type": "file_public", "file_id": "F2147483862", "file": {"id": "F2147483862"}}"#,
+        ),
+        (
+            "FilePrivate",
+            r#"{"type": "file_private", "file": {"id": "F2147483862"}}"#,
+        ),
+        (
+            "FileChange",
+            r#"{"type": "file_change", "file_id": "F2147483862", "file": {"id": "F2147483862"}}"#,
+        ),
+        (
+            "FileDeleted",
+            r#"{"type": "file_deleted", "file_id": "F2147483862", "event_ts": "1361482916.000004"}"#,
+        ),
+        (
+            "FileCommentAdded",
+            r#"{"type": "file_comment_added", "file": {"id": "F2147483862"}, "comment": {"id": "Fc67890", "comment": "nice", "user": "U024BE7LH"}}"#,
+        ),
+        (
+            "FileCommentEdited",
+            r#"{"type": "file_comment_edited", "file": {"id": "F2147483862"}, "comment": {"id": "Fc67890", "comment": "nicer", "user": "U024BE7LH"}}"#,
+        ),
+        (
+            "FileCommentDeleted",
+            r#"{"type": "file_comment_deleted", "file": {"id": "F2147483862"}, "comment": "Fc67890"}"#,
+        ),
+        (
+            "PinAdded",
+            r#"{"type": "pin_added", "user": "U024BE7LH", "channel_id": "C02ELGNBH", "item": {"type": "message", "channel": "C02ELGNBH"}, "event_ts": "1360782804.083113"}"#,
+        ),
+        (
+            "PinRemoved",
+            r#"{"type": "pin_removed", "user": "U024BE7LH", "channel_id": "C02ELGNBH", "item": {"type": "message", "channel": "C02ELGNBH"}, "has_pins": false, "event_ts": "1360782845.083113"}"#,
+        ),
+        (
+            "PresenceChange",
+            r#"{"type": "presence_change", "user": "U024BE7LH", "presence": "away"}"#,
+        ),
+        (
+            "ManualPresenceChange",
+            r#"{"type": "manual_presence_change", "presence": "away"}"#,
+        ),
+        (
+            "PrefChange",
+            r#"{"type": "pref_change", "name": "messages_theme", "value": "dense"}"#,
+        ),
+        (
+            "UserChange",
+            r#"{"type": "user_change", "user": {"id": "U024BE7LH", "name": "bobby"}}"#,
+        ),
+        (
+            "TeamJoin",
+            r#"{"type": "team_join", "user": {"id": "U024BE7LH", "name": "bobby"}}"#,
+        ),
+        (
+            "StarAdded",
+            r#"{"type": "star_added", "user": "U024BE7LH", "item": {"type": "channel", "channel": "C2147483705"}, "event_ts": "1360782804.083113"}"#,
+        ),
+        (
+            "StarRemoved",
+            r#"{"type": "star_removed", "user": "U024BE7LH", "item": {"type": "channel", "channel": "C2147483705"}, "event_ts": "1360782804.083113"}"#,
+        ),
+        (
+            "ReactionAdded",
+            r#"{"type": "reaction_added", "user": "U024BE7LH", "reaction": "thumbsup", "item_user": "U0G9QF9C6", "item": {"type": "message", "channel": "C0G9QF9GZ", "ts": "1360782400.498405"}, "event_ts": "1360782804.083113"}"#,
+        ),
+        (
+            "ReactionRemoved",
+            r#"{"type": "reaction_removed", "user": "U024BE7LH", "reaction": "thumbsup", "item_user": "U0G9QF9C6", "item": {"type": "message", "channel": "C0G9QF9GZ", "ts": "1360782400.498405"}, "event_ts": "1360782804.083113"}"#,
+        ),
+        (
+            "EmojiChanged",
+            r#"{"type": "emoji_changed", "subtype": "add", "name": "picard_facepalm", "event_ts": "1361482916.000004"}"#,
+        ),
+        (
+            "CommandsChanged",
+            r#"{"type": "commands_changed", "event_ts": "1361482916.000004"}"#,
+        ),
+        (
+            "TeamPlanChange",
+            r#"{"type": "team_plan_change", "plan": "std"}"#,
+        ),
+        (
+            "TeamPrefChange",
+            r#"{"type": "team_pref_change", "name": "slackbot_responses_only_admins", "value": true}"#,
+        ),
+        (
+            "TeamRename",
+            r#"{"type": "team_rename", "name": "New Team Name Inc."}"#,
+        ),
+        (
+            "TeamDomainChange",
+            r#"{"type": "team_domain_change", "url": "https://my.slack.com", "domain": "my"}"#,
+        ),
+        (
+            "EmailDomainChanged",
+            r#"{"type": "email_domain_changed", "email_domain": "example.com", "event_ts": "1360782804.083113"}"#,
+        ),
+        (
+            "BotAdded",
+            r#"{"type": "bot_added", "bot": {"id": "B024BE7LH", "name": "hugbot", "icons": {"image_48": "https://example.com/48.png"}}}"#,
+        ),
+        (
+            "BotChanged",
+            r#"{"type": "bot_changed", "bot": {"id": "B024BE7LH", "name": "hugbot"}}"#,
+        ),
+        ("AccountsChanged", r#"{"type": "accounts_changed"}"#),
+        (
+            "TeamMigrationStarted",
+            r#"{"type": "team_migration_started"}"#,
+        ),
+        (
+            "ReconnectUrl",
+            r#"{"type": "reconnect_url", "url": "wss://example.slack-msgs.com/websocket/abc"}"#,
+        ),
+        (
+            "MessageSent",
+            r#"{"ok": true, "reply_to": 1, "ts": "1355517523.000005", "text": "Hello world"}"#,
+        ),
+        (
+            "MessageError",
+            r#"{"ok": false, "reply_to": 1, "error": {"code": 2, "msg": "message text is missing"}}"#,
+        ),
+        (
+            "DesktopNotification",
+            r#"{"type": "desktop_notification", "title": "Team", "subtitle": "general", "msg": "1355517523.000005", "content": "bobby: hi", "channel": "C2147483705", "launchUri": "slack://channel?id=C2147483705", "avatarImage": "https://example.com/avatar.png", "ssbFilename": "knock_brush.mp3", "imageUri": null, "is_shared": false, "event_ts": "1355517524.000005"}"#,
+        ),
+        ("Unknown", r#"{"type": "some_future_event", "detail": 1}"#),
+    ];
+
+    #[test]
+    fn decode_every_variant() {
+        for (expected, json) in SAMPLES {
+            let event = Event::from_json(json).unwrap_or_else(|e| panic!("{}: {}", expected, e));
+            assert_eq!(variant(&event), *expected, "{}", json);
+        }
+    }
+
+    #[test]
+    fn every_variant_has_a_sample() {
+        let covered = SAMPLES
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<std::collections::HashSet<_>>();
+        // one sample per arm of `variant`
+        assert_eq!(covered.len(), SAMPLES.len());
+        assert_eq!(SAMPLES.len(), 66);
+    }
 }
@@ -0,0 +1,213 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Parsing of the markup in message text.
+//!
+//! Slack encodes mentions and links in message `text` as `<...>` entities,
+//! e.g. `<@U024BE7LH>`, `<#C024BE7LR|general>` or
+//! `<https://example.com|a link>`, and escapes `&`, `<` and `>`.
+
+/// A piece of message text
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MarkupToken {
+    /// Plain text, with `&amp;`, `&lt;` and `&gt;` unescaped
+    Text(String),
+    /// `<@U024BE7LH>`, a user id and optional label
+    UserMention(String, Option<String>),
+    /// `<#C024BE7LR|general>`, a channel id and optional label
+    ChannelMention(String, Option<String>),
+    /// `<https://example.com|label>`, a url and optional label
+    Link(String, Option<String>),
+    /// `:smile:`, an emoji name without the colons
+    Emoji(String),
+    /// `<!here>` or `<!subteam^S012|@team>`, a special command and optional label
+    Special(String, Option<String>),
+}
+
+/// Splits message text into its markup tokens
+pub fn parse(text: &str) -> Vec<MarkupToken> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        let end = match rest[start..].find('>') {
+            Some(end) => start + end,
+            None => break,
+        };
+        parse_text(&rest[..start], &mut tokens);
+        tokens.push(parse_entity(&rest[start + 1..end]));
+        rest = &rest[end + 1..];
+    }
+    parse_text(rest, &mut tokens);
+    tokens
+}
+
+/// Renders message text as plain text, resolving user and channel ids to
+/// names with `resolve`.
+///
+/// Labels embedded in the text take precedence over `resolve`; ids that
+/// resolve to `None` are kept as is.
+pub fn to_plain<F>(text: &str, resolve: F) -> String
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut plain = String::with_capacity(text.len());
+    for token in parse(text) {
+        match token {
+            MarkupToken::Text(text) => plain.push_str(&text),
+            MarkupToken::UserMention(id, label) => {
+                plain.push('@');
+                plain.push_str(&label.or_else(|| resolve(&id)).unwrap_or(id));
+            }
+            MarkupToken::ChannelMention(id, label) => {
+                plain.push('#');
+                plain.push_str(&label.or_else(|| resolve(&id)).unwrap_or(id));
+            }
+            MarkupToken::Link(url, label) => plain.push_str(&label.unwrap_or(url)),
+            MarkupToken::Emoji(name) => {
+                plain.push(':');
+                plain.push_str(&name);
+                plain.push(':');
+            }
+            MarkupToken::Special(_, Some(label)) => plain.push_str(&label),
+            MarkupToken::Special(name, None) => {
+                plain.push('@');
+                plain.push_str(&name);
+            }
+        }
+    }
+    plain
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/// Parses the inside of a `<...>` entity
+fn parse_entity(entity: &str) -> MarkupToken {
+    let (target, label) = match entity.find('|') {
+        Some(bar) => (&entity[..bar], Some(unescape(&entity[bar + 1..]))),
+        None => (entity, None),
+    };
+    if let Some(id) = target.strip_prefix('@') {
+        MarkupToken::UserMention(id.to_string(), label)
+    } else if let Some(id) = target.strip_prefix('#') {
+        MarkupToken::ChannelMention(id.to_string(), label)
+    } else if let Some(name) = target.strip_prefix('!') {
+        MarkupToken::Special(name.to_string(), label)
+    } else {
+        MarkupToken::Link(unescape(target), label)
+    }
+}
+
+fn is_emoji_char(c: char) -> bool {
+    c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-' || c == '+'
+}
+
+/// Splits plain text into `Text` and `Emoji` tokens
+fn parse_text(text: &str, tokens: &mut Vec<MarkupToken>) {
+    let mut plain_start = 0;
+    let mut search = 0;
+    while let Some(open) = text[search..].find(':').map(|i| search + i) {
+        let name_len = text[open + 1..]
+            .find(|c: char| !is_emoji_char(c))
+            .unwrap_or(text.len() - open - 1);
+        let close = open + 1 + name_len;
+        // `10:30:00` is a time, not an emoji
+        let after_word = matches!(text[..open].chars().next_back(), Some(c) if c.is_alphanumeric());
+        if name_len > 0 && !after_word && text[close..].starts_with(':') {
+            if plain_start < open {
+                tokens.push(MarkupToken::Text(unescape(&text[plain_start..open])));
+            }
+            tokens.push(MarkupToken::Emoji(text[open + 1..close].to_string()));
+            plain_start = close + 1;
+            search = close + 1;
+        } else {
+            search = open + 1;
+        }
+    }
+    if plain_start < text.len() {
+        tokens.push(MarkupToken::Text(unescape(&text[plain_start..])));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MarkupToken::*;
+    use super::*;
+
+    #[test]
+    fn parse_entities() {
+        let tokens = parse("Hey <@U024BE7LH|bob>, see <#C024BE7LR|general> and <https://example.com/?a=1&amp;b=2|the docs> :tada:");
+        assert_eq!(
+            tokens,
+            vec![
+                Text("Hey ".into()),
+                UserMention("U024BE7LH".into(), Some("bob".into())),
+                Text(", see ".into()),
+                ChannelMention("C024BE7LR".into(), Some("general".into())),
+                Text(" and ".into()),
+                Link(
+                    "https://example.com/?a=1&b=2".into(),
+                    Some("the docs".into())
+                ),
+                Text(" ".into()),
+                Emoji("tada".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_plain_text() {
+        assert_eq!(
+            parse("meet at 10:30:00 &lt;sharp&gt; :not an emoji"),
+            vec![Text("meet at 10:30:00 <sharp> :not an emoji".into())]
+        );
+        assert_eq!(
+            parse("unterminated <@U1"),
+            vec![Text("unterminated <@U1".into())]
+        );
+        assert!(parse("").is_empty());
+    }
+
+    #[test]
+    fn parse_special() {
+        assert_eq!(
+            parse("<!here> <!subteam^SAZ94GDB8|@ops>"),
+            vec![
+                Special("here".into(), None),
+                Text(" ".into()),
+                Special("subteam^SAZ94GDB8".into(), Some("@ops".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn plain_text_resolves_ids() {
+        let resolve = |id: &str| match id {
+            "U024BE7LH" => Some("bob".to_string()),
+            _ => None,
+        };
+        assert_eq!(
+            to_plain(
+                "<!here> <@U024BE7LH> <@U0UNKNOWN> joined <#C024BE7LR|general> via <mailto:bob@example.com|bob@example.com> :wave:",
+                resolve
+            ),
+            "@here @bob @U0UNKNOWN joined #general via bob@example.com :wave:"
+        );
+    }
+}
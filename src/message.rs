@@ -0,0 +1,456 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use crate::api::{Message, Timestamp};
+use crate::markup::{self, MarkupToken};
+
+/// Matches `$msg` against each listed `Message` variant and evaluates `$get`
+/// on the inner struct, falling back to `None` for unlisted variants.
+macro_rules! message_field {
+    ($msg:expr, |$m:ident| $get:expr, [$($variant:ident),* $(,)?]) => {
+        match *$msg {
+            $(Message::$variant(ref $m) => $get,)*
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    };
+}
+
+/// Access to the fields common to most `Message` subtypes, without matching
+/// on every variant.
+///
+/// Each method returns `None` for subtypes that don't carry the field.
+pub trait MessageExt {
+    /// The message's timestamp, which is also its id within the channel
+    fn ts(&self) -> Option<Timestamp>;
+    /// The id of the channel the message was posted in
+    fn channel(&self) -> Option<&str>;
+    /// The id of the user who posted the message
+    fn user(&self) -> Option<&str>;
+    /// The message text
+    fn text(&self) -> Option<&str>;
+    /// The `ts` of the thread's parent message, if the message belongs to a thread
+    fn thread_ts(&self) -> Option<Timestamp>;
+    /// Whether Slack posted the message to record a change to the channel:
+    /// someone joining or leaving, or the channel being renamed, archived or
+    /// unarchived, or getting a new topic or purpose
+    fn is_system(&self) -> bool;
+    /// Whether someone posted the message: a plain message, a `/me`
+    /// message, a bot message, a shared file or a thread reply broadcast
+    /// to the channel
+    fn is_user_message(&self) -> bool;
+    /// The ids of the users `@`-mentioned in the text, e.g. `U024BE7LH` for
+    /// `<@U024BE7LH>`, each once and in order of appearance
+    fn mentioned_users(&self) -> Vec<String>;
+    /// Whether the text `@`-mentions `user_id`, e.g. the bot's own id from
+    /// the `StartResponse`
+    fn mentions_user(&self, user_id: &str) -> bool;
+}
+
+impl MessageExt for Message {
+    fn ts(&self) -> Option<Timestamp> {
+        message_field!(
+            self,
+            |m| m.ts,
+            [
+                Standard,
+                BotAdd,
+                BotDisable,
+                BotEnable,
+                BotRemove,
+                BotMessage,
+                ChannelArchive,
+                ChannelJoin,
+                ChannelLeave,
+                ChannelName,
+                ChannelPurpose,
+                ChannelTopic,
+                ChannelUnarchive,
+                FileComment,
+                FileMention,
+                FileShare,
+                GroupArchive,
+                GroupJoin,
+                GroupLeave,
+                GroupName,
+                GroupPurpose,
+                GroupTopic,
+                GroupUnarchive,
+                MeMessage,
+                MessageChanged,
+                MessageDeleted,
+                MessageReplied,
+                PinnedItem,
+                ReminderAdd,
+                ReplyBroadcast,
+                ThreadBroadcast,
+                UnpinnedItem,
+                ShRoomCreated,
+                SlackbotResponse,
+            ]
+        )
+    }
+
+    fn channel(&self) -> Option<&str> {
+        message_field!(
+            self,
+            |m| m.channel.as_deref(),
+            [
+                Standard,
+                BotAdd,
+                BotDisable,
+                BotEnable,
+                BotRemove,
+                BotMessage,
+                MeMessage,
+                MessageChanged,
+                MessageDeleted,
+                MessageReplied,
+                PinnedItem,
+                ReminderAdd,
+                ReplyBroadcast,
+                UnpinnedItem,
+                ShRoomCreated,
+                SlackbotResponse,
+            ]
+        )
+    }
+
+    fn user(&self) -> Option<&str> {
+        match *self {
+            Message::FileComment(ref m) => m.comment.as_ref().and_then(|c| c.user.as_deref()),
+            Message::MessageChanged(ref m) => m.message.as_ref().and_then(|m| m.user.as_deref()),
+            Message::MessageReplied(ref m) => m.message.as_ref().and_then(|m| m.user.as_deref()),
+            _ => message_field!(
+                self,
+                |m| m.user.as_deref(),
+                [
+                    Standard,
+                    BotAdd,
+                    BotDisable,
+                    BotEnable,
+                    BotRemove,
+                    ChannelArchive,
+                    ChannelJoin,
+                    ChannelLeave,
+                    ChannelName,
+                    ChannelPurpose,
+                    ChannelTopic,
+                    ChannelUnarchive,
+                    FileMention,
+                    FileShare,
+                    GroupArchive,
+                    GroupJoin,
+                    GroupLeave,
+                    GroupName,
+                    GroupPurpose,
+                    GroupTopic,
+                    GroupUnarchive,
+                    MeMessage,
+                    PinnedItem,
+                    ReminderAdd,
+                    ReplyBroadcast,
+                    ThreadBroadcast,
+                    UnpinnedItem,
+                    ShRoomCreated,
+                    SlackbotResponse,
+                ]
+            ),
+        }
+    }
+
+    fn text(&self) -> Option<&str> {
+        match *self {
+            Message::MessageChanged(ref m) => m.message.as_ref().and_then(|m| m.text.as_deref()),
+            Message::MessageReplied(ref m) => m.message.as_ref().and_then(|m| m.text.as_deref()),
+            _ => message_field!(
+                self,
+                |m| m.text.as_deref(),
+                [
+                    Standard,
+                    BotAdd,
+                    BotDisable,
+                    BotEnable,
+                    BotRemove,
+                    BotMessage,
+                    ChannelArchive,
+                    ChannelJoin,
+                    ChannelLeave,
+                    ChannelName,
+                    ChannelPurpose,
+                    ChannelTopic,
+                    ChannelUnarchive,
+                    FileComment,
+                    FileMention,
+                    FileShare,
+                    GroupArchive,
+                    GroupJoin,
+                    GroupLeave,
+                    GroupName,
+                    GroupPurpose,
+                    GroupTopic,
+                    GroupUnarchive,
+                    MeMessage,
+                    PinnedItem,
+                    ReminderAdd,
+                    UnpinnedItem,
+                    ShRoomCreated,
+                    SlackbotResponse,
+                ]
+            ),
+        }
+    }
+
+    fn thread_ts(&self) -> Option<Timestamp> {
+        match *self {
+            Message::MessageChanged(ref m) => m.message.as_ref().and_then(|m| m.thread_ts),
+            _ => message_field!(
+                self,
+                |m| m.thread_ts,
+                [
+                    Standard,
+                    BotMessage,
+                    MessageReplied,
+                    ThreadBroadcast,
+                    SlackbotResponse,
+                ]
+            ),
+        }
+    }
+
+    fn is_system(&self) -> bool {
+        matches!(
+            *self,
+            Message::ChannelArchive(_)
+                | Message::ChannelJoin(_)
+                | Message::ChannelLeave(_)
+                | Message::ChannelName(_)
+                | Message::ChannelPurpose(_)
+                | Message::ChannelTopic(_)
+                | Message::ChannelUnarchive(_)
+                | Message::GroupArchive(_)
+                | Message::GroupJoin(_)
+                | Message::GroupLeave(_)
+                | Message::GroupName(_)
+                | Message::GroupPurpose(_)
+                | Message::GroupTopic(_)
+                | Message::GroupUnarchive(_)
+        )
+    }
+
+    fn is_user_message(&self) -> bool {
+        matches!(
+            *self,
+            Message::Standard(_)
+                | Message::MeMessage(_)
+                | Message::BotMessage(_)
+                | Message::FileShare(_)
+                | Message::ThreadBroadcast(_)
+        )
+    }
+
+    fn mentioned_users(&self) -> Vec<String> {
+        let mut users = Vec::new();
+        for token in markup::parse(self.text().unwrap_or("")) {
+            if let MarkupToken::UserMention(id, _) = token {
+                if !users.contains(&id) {
+                    users.push(id);
+                }
+            }
+        }
+        users
+    }
+
+    fn mentions_user(&self, user_id: &str) -> bool {
+        markup::parse(self.text().unwrap_or(""))
+            .iter()
+            .any(|token| matches!(*token, MarkupToken::UserMention(ref id, _) if id == user_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(json: &str) -> Message {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn standard_message_fields() {
+        let msg = message(
+            r#"{
+            "type": "message",
+            "ts": "1234567890.218332",
+            "user": "U12345678",
+            "text": "Hello world",
+            "channel": "C12345678"
+        }"#,
+        );
+        assert_eq!(msg.ts().unwrap().to_param_value(), "1234567890.218332");
+        assert_eq!(msg.channel(), Some("C12345678"));
+        assert_eq!(msg.user(), Some("U12345678"));
+        assert_eq!(msg.text(), Some("Hello world"));
+    }
+
+    #[test]
+    fn message_changed_fields() {
+        let msg = message(
+            r#"{
+            "type": "message",
+            "subtype": "message_changed",
+            "hidden": true,
+            "channel": "C2147483705",
+            "ts": "1358878755.000001",
+            "message": {
+                "type": "message",
+                "user": "U2147483697",
+                "text": "Hello, world!",
+                "ts": "1355517523.000005"
+            }
+        }"#,
+        );
+        assert_eq!(msg.ts().unwrap().to_param_value(), "1358878755.000001");
+        assert_eq!(msg.channel(), Some("C2147483705"));
+        assert_eq!(msg.user(), Some("U2147483697"));
+        assert_eq!(msg.text(), Some("Hello, world!"));
+    }
+
+    #[test]
+    fn missing_fields_are_none() {
+        let msg = message(
+            r#"{
+            "type": "message",
+            "subtype": "bot_message",
+            "ts": "1358877455.000010",
+            "text": "Pushing is the answer",
+            "bot_id": "BB12033",
+            "username": "github"
+        }"#,
+        );
+        assert_eq!(msg.text(), Some("Pushing is the answer"));
+        assert_eq!(msg.user(), None);
+        assert_eq!(msg.channel(), None);
+    }
+
+    #[test]
+    fn thread_ts_of_reply() {
+        let msg = message(
+            r#"{
+            "type": "message",
+            "channel": "C061EG9SL",
+            "user": "U061F7AUR",
+            "text": "in a thread",
+            "ts": "1483037603.017503",
+            "thread_ts": "1482960137.003543"
+        }"#,
+        );
+        assert_eq!(
+            msg.thread_ts().unwrap().to_param_value(),
+            "1482960137.003543"
+        );
+    }
+
+    #[test]
+    fn system_and_user_messages() {
+        let join = message(
+            r#"{
+            "type": "message",
+            "subtype": "channel_join",
+            "ts": "1358877458.000011",
+            "user": "U2147483828",
+            "text": "<@U2147483828|cal> has joined the channel"
+        }"#,
+        );
+        assert!(join.is_system());
+        assert!(!join.is_user_message());
+
+        let topic = message(
+            r#"{
+            "type": "message",
+            "subtype": "group_topic",
+            "ts": "1358877455.000010",
+            "user": "U2147483828",
+            "topic": "hello world",
+            "text": "<@U2147483828|cal> set the group topic: hello world"
+        }"#,
+        );
+        assert!(topic.is_system());
+
+        let me = message(
+            r#"{
+            "type": "message",
+            "subtype": "me_message",
+            "channel": "C2147483705",
+            "user": "U2147483697",
+            "text": "is doing that thing",
+            "ts": "1355517523.000005"
+        }"#,
+        );
+        assert!(me.is_user_message());
+        assert!(!me.is_system());
+
+        let changed = message(
+            r#"{
+            "type": "message",
+            "subtype": "message_deleted",
+            "hidden": true,
+            "channel": "C2147483705",
+            "ts": "1358878755.000001",
+            "deleted_ts": "1358878749.000002"
+        }"#,
+        );
+        assert!(!changed.is_system());
+        assert!(!changed.is_user_message());
+    }
+
+    #[test]
+    fn mentions() {
+        let msg = message(
+            r#"{
+            "type": "message",
+            "ts": "1234567890.218332",
+            "user": "U12345678",
+            "text": "<@U0BOT1234> deploy, cc <@U024BE7LH|bob> and <@U0BOT1234>",
+            "channel": "C12345678"
+        }"#,
+        );
+        assert_eq!(msg.mentioned_users(), ["U0BOT1234", "U024BE7LH"]);
+        assert!(msg.mentions_user("U0BOT1234"));
+        assert!(msg.mentions_user("U024BE7LH"));
+        assert!(!msg.mentions_user("U12345678"));
+    }
+
+    #[test]
+    fn no_mentions() {
+        let msg = message(
+            r#"{
+            "type": "message",
+            "ts": "1234567890.218332",
+            "user": "U12345678",
+            "text": "mail @U0BOT1234 in <#C024BE7LR|general>, <!here>",
+            "channel": "C12345678"
+        }"#,
+        );
+        assert!(msg.mentioned_users().is_empty());
+        assert!(!msg.mentions_user("U0BOT1234"));
+
+        let deleted = message(
+            r#"{"type": "message", "subtype": "message_deleted", "ts": "1.000001", "deleted_ts": "1.000000"}"#,
+        );
+        assert!(deleted.mentioned_users().is_empty());
+    }
+}
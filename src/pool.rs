@@ -0,0 +1,218 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Running the receive loops of many workspaces from one place.
+
+use crate::{Error, EventHandler, RtmClient, RunConfig, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// How long a client waits before logging in again after an error, unless
+/// changed with `RtmPool::retry_delay`
+pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Senders of the connected clients, and whether the pool is shutting down
+#[derive(Default)]
+struct State {
+    stopping: bool,
+    senders: Vec<Option<Sender>>,
+}
+
+/// Runs one `RtmClient` per token, each on its own thread, e.g. for a bot
+/// installed in many workspaces.
+///
+/// A client whose login or `run` fails logs in and runs again after the
+/// retry delay, unless Slack rejected its token; `run` returning `Ok`, as
+/// after `shutdown_all`, ends it.
+pub struct RtmPool {
+    max_clients: usize,
+    config: RunConfig,
+    retry_delay: Duration,
+    state: Arc<Mutex<State>>,
+    threads: Vec<thread::JoinHandle<()>>,
+}
+
+impl RtmPool {
+    /// A pool running up to `max_clients` clients with `config`
+    pub fn new(max_clients: usize, config: RunConfig) -> RtmPool {
+        RtmPool {
+            max_clients,
+            config,
+            retry_delay: DEFAULT_RETRY_DELAY,
+            state: Default::default(),
+            threads: Vec::new(),
+        }
+    }
+
+    /// Sets how long a client waits before logging in again after an error
+    pub fn retry_delay(mut self, retry_delay: Duration) -> RtmPool {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Logs in with `token` and runs `handler` on a new thread.
+    ///
+    /// Fails if the pool already runs `max_clients` clients.
+    pub fn add<T>(&mut self, token: &str, handler: T) -> Result<(), Error>
+    where
+        T: EventHandler + Send + 'static,
+    {
+        let token = token.to_string();
+        self.add_with(move || RtmClient::login(&token), handler)
+    }
+
+    /// Like `add`, getting each connection from `login`
+    pub(crate) fn add_with<L, T>(&mut self, mut login: L, mut handler: T) -> Result<(), Error>
+    where
+        L: FnMut() -> Result<RtmClient, Error> + Send + 'static,
+        T: EventHandler + Send + 'static,
+    {
+        if self.threads.len() >= self.max_clients {
+            return Err(Error::Internal(format!(
+                "RtmPool is full ({} clients)",
+                self.max_clients
+            )));
+        }
+        let slot = {
+            let mut state = self.state.lock().unwrap();
+            state.senders.push(None);
+            state.senders.len() - 1
+        };
+        let state = self.state.clone();
+        let config = self.config.clone();
+        let retry_delay = self.retry_delay;
+        self.threads.push(thread::spawn(move || loop {
+            let result = login().and_then(|client| {
+                {
+                    let mut state = state.lock().unwrap();
+                    if state.stopping {
+                        return Ok(());
+                    }
+                    state.senders[slot] = Some(client.sender());
+                }
+                client.run_with_config(&mut handler, &config)
+            });
+            state.lock().unwrap().senders[slot] = None;
+            match result {
+                Ok(()) => return,
+                Err(err) => {
+                    if let Some(code) = err.auth_failure() {
+                        handler.on_auth_failed(code);
+                        return;
+                    }
+                    warn!("RtmPool client failed, retrying: {}", err);
+                }
+            }
+            thread::sleep(retry_delay);
+            if state.lock().unwrap().stopping {
+                return;
+            }
+        }));
+        Ok(())
+    }
+
+    /// Number of clients added
+    pub fn len(&self) -> usize {
+        self.threads.len()
+    }
+
+    /// Whether no client was added
+    pub fn is_empty(&self) -> bool {
+        self.threads.is_empty()
+    }
+
+    /// Shuts down every connected client and stops the others from
+    /// reconnecting. Use `join` to wait for them.
+    pub fn shutdown_all(&self) -> Result<(), Error> {
+        let mut state = self.state.lock().unwrap();
+        state.stopping = true;
+        for sender in state.senders.iter().flatten() {
+            sender.shutdown()?;
+        }
+        Ok(())
+    }
+
+    /// Waits until every client has stopped
+    pub fn join(self) {
+        for thread in self.threads {
+            if thread.join().is_err() {
+                error!("RtmPool client thread panicked");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{local_client, NoopHandler};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn full_pool_rejects_clients() {
+        let mut pool = RtmPool::new(0, Default::default());
+        assert!(pool.add("xoxb-token", NoopHandler).is_err());
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn retries_then_shuts_down_all() {
+        let mut pool = RtmPool::new(2, Default::default()).retry_delay(Duration::from_millis(10));
+        let logins = Arc::new(AtomicUsize::new(0));
+        for _ in 0..2 {
+            let logins = logins.clone();
+            pool.add_with(
+                move || {
+                    if logins.fetch_add(1, Ordering::SeqCst) == 0 {
+                        Err(Error::Internal("first login fails".into()))
+                    } else {
+                        Ok(local_client())
+                    }
+                },
+                NoopHandler,
+            )
+            .unwrap();
+        }
+        assert_eq!(pool.len(), 2);
+        while pool.state.lock().unwrap().senders.iter().flatten().count() < 2 {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(logins.load(Ordering::SeqCst), 3);
+        pool.shutdown_all().unwrap();
+        pool.join();
+    }
+
+    #[test]
+    fn auth_failure_is_not_retried() {
+        let mut pool = RtmPool::new(1, Default::default()).retry_delay(Duration::from_millis(10));
+        let logins = Arc::new(AtomicUsize::new(0));
+        let counted = logins.clone();
+        pool.add_with(
+            move || {
+                counted.fetch_add(1, Ordering::SeqCst);
+                Err(Error::Slack {
+                    method: "rtm.start".into(),
+                    code: "invalid_auth".into(),
+                })
+            },
+            NoopHandler,
+        )
+        .unwrap();
+        pool.join();
+        assert_eq!(logins.load(Ordering::SeqCst), 1);
+    }
+}
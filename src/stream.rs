@@ -0,0 +1,262 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Websocket transport for the RTM connection.
+//!
+//! TLS is provided by `native-tls` with the `with_native_tls` feature, or by
+//! `rustls` with the `with_rustls` feature. When both are enabled `native-tls`
+//! is used.
+
+use crate::error::Error;
+use percent_encoding::percent_decode_str;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use tungstenite::handshake::client::Response;
+use tungstenite::handshake::HandshakeError;
+use tungstenite::protocol::WebSocketConfig;
+use tungstenite::stream::Mode;
+use tungstenite::WebSocket;
+
+#[cfg(feature = "with_native_tls")]
+type TlsStream = native_tls::TlsStream<TcpStream>;
+
+#[cfg(all(feature = "with_rustls", not(feature = "with_native_tls")))]
+type TlsStream = rustls::StreamOwned<rustls::ClientSession, TcpStream>;
+
+/// A plain or TLS wrapped TCP stream
+pub type Stream = tungstenite::stream::Stream<TcpStream, TlsStream>;
+
+/// Connects to `url` and performs the websocket handshake.
+///
+/// With a `proxy` the connection is tunneled through it using HTTP `CONNECT`.
+/// `config` overrides tungstenite's default message and frame size limits.
+pub fn connect(
+    url: &url::Url,
+    proxy: Option<&url::Url>,
+    config: Option<WebSocketConfig>,
+) -> Result<(WebSocket<Stream>, Response), Error> {
+    let mode = tungstenite::client::url_mode(url)?;
+    let (host, port) = host_and_port(url)?;
+    let tcp = match proxy {
+        Some(proxy) => {
+            if proxy.scheme() != "http" {
+                return Err(Error::Internal(format!(
+                    "Unsupported proxy scheme {}, only http proxies can be used",
+                    proxy.scheme()
+                )));
+            }
+            let mut tcp = TcpStream::connect(host_and_port(proxy)?)?;
+            tunnel(&mut tcp, proxy, host, port)?;
+            tcp
+        }
+        None => TcpStream::connect((host, port))?,
+    };
+    tcp.set_nodelay(true)?;
+    let stream = match mode {
+        Mode::Plain => Stream::Plain(tcp),
+        Mode::Tls => Stream::Tls(wrap_tls(host, tcp)?),
+    };
+    tungstenite::client::client_with_config(url.clone(), stream, config).map_err(|e| match e {
+        HandshakeError::Failure(e) => e.into(),
+        HandshakeError::Interrupted(_) => Error::Internal("Websocket handshake interrupted".into()),
+    })
+}
+
+fn host_and_port(url: &url::Url) -> Result<(&str, u16), Error> {
+    let host = url
+        .host_str()
+        .ok_or_else(|| Error::Internal(format!("No host in url: {}", url)))?;
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| Error::Internal(format!("No port in url: {}", url)))?;
+    Ok((host, port))
+}
+
+/// Longest proxy response to `CONNECT` that is read, headers included
+const MAX_PROXY_RESPONSE: usize = 8192;
+
+/// Asks the proxy `tcp` is connected to for a tunnel to `host:port`
+fn tunnel(tcp: &mut TcpStream, proxy: &url::Url, host: &str, port: u16) -> Result<(), Error> {
+    let mut request = format!(
+        "CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n",
+        host = host,
+        port = port
+    );
+    if !proxy.username().is_empty() {
+        // `Url` keeps them percent-encoded, e.g. `@` as `%40`
+        let decode = |s| percent_decode_str(s).collect::<Vec<u8>>();
+        let mut credentials = decode(proxy.username());
+        credentials.push(b':');
+        credentials.extend(decode(proxy.password().unwrap_or_default()));
+        request.push_str(&format!(
+            "Proxy-Authorization: Basic {}\r\n",
+            base64::encode(credentials)
+        ));
+    }
+    request.push_str("\r\n");
+    tcp.write_all(request.as_bytes())?;
+
+    // read byte by byte so nothing after the proxy's response is consumed
+    let mut response = Vec::new();
+    let mut byte = [0u8; 1];
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() >= MAX_PROXY_RESPONSE {
+            return Err(Error::Internal(format!(
+                "Proxy response to CONNECT is longer than {} bytes",
+                MAX_PROXY_RESPONSE
+            )));
+        }
+        if tcp.read(&mut byte)? == 0 {
+            return Err(Error::Internal(
+                "Proxy closed the connection during CONNECT".into(),
+            ));
+        }
+        response.push(byte[0]);
+    }
+    let response = String::from_utf8_lossy(&response);
+    let status_line = response.lines().next().unwrap_or_default();
+    match status_line.split_whitespace().nth(1) {
+        Some(code) if code.starts_with('2') => Ok(()),
+        _ => Err(Error::Internal(format!(
+            "Proxy refused CONNECT to {}:{}: {}",
+            host, port, status_line
+        ))),
+    }
+}
+
+/// The TCP stream underlying `stream`
+pub fn tcp_stream(stream: &Stream) -> &TcpStream {
+    match *stream {
+        Stream::Plain(ref s) => s,
+        Stream::Tls(ref t) => t.get_ref(),
+    }
+}
+
+#[cfg(feature = "with_native_tls")]
+fn wrap_tls(host: &str, tcp: TcpStream) -> Result<TlsStream, Error> {
+    let connector = native_tls::TlsConnector::new().map_err(tungstenite::Error::Tls)?;
+    connector.connect(host, tcp).map_err(|e| match e {
+        native_tls::HandshakeError::Failure(e) => tungstenite::Error::Tls(e).into(),
+        native_tls::HandshakeError::WouldBlock(_) => {
+            Error::Internal("TLS handshake interrupted".into())
+        }
+    })
+}
+
+#[cfg(all(feature = "with_rustls", not(feature = "with_native_tls")))]
+fn wrap_tls(host: &str, tcp: TcpStream) -> Result<TlsStream, Error> {
+    use std::sync::Arc;
+
+    let mut config = rustls::ClientConfig::new();
+    config
+        .root_store
+        .add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+    let dns_name = webpki::DNSNameRef::try_from_ascii_str(host)
+        .map_err(|_| Error::Internal(format!("Invalid websocket host name: {}", host)))?;
+    let session = rustls::ClientSession::new(&Arc::new(config), dns_name);
+    Ok(rustls::StreamOwned::new(session, tcp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Runs a one-shot proxy answering `response`, returning its url and the
+    /// request it received
+    fn proxy(response: &str) -> (url::Url, thread::JoinHandle<String>) {
+        let response = response.to_string();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = thread::spawn(move || {
+            let (mut conn, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 256];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = conn.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            conn.write_all(response.as_bytes()).unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        let url = url::Url::parse(&format!("http://user:secret@{}", addr)).unwrap();
+        (url, handle)
+    }
+
+    #[test]
+    fn tunnel_sends_connect() {
+        let (url, handle) = proxy("HTTP/1.1 200 Connection established\r\n\r\n");
+        let mut tcp = TcpStream::connect(host_and_port(&url).unwrap()).unwrap();
+        tunnel(&mut tcp, &url, "cerberus-xxxx.lb.slack-msgs.com", 443).unwrap();
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("CONNECT cerberus-xxxx.lb.slack-msgs.com:443 HTTP/1.1\r\n"));
+        assert!(request.contains("Proxy-Authorization: Basic dXNlcjpzZWNyZXQ=\r\n"));
+    }
+
+    #[test]
+    fn tunnel_decodes_credentials() {
+        let (mut url, handle) = proxy("HTTP/1.1 200 Connection established\r\n\r\n");
+        url.set_username("us@r").unwrap();
+        url.set_password(Some("p:ss%")).unwrap();
+        let mut tcp = TcpStream::connect(host_and_port(&url).unwrap()).unwrap();
+        tunnel(&mut tcp, &url, "example.com", 443).unwrap();
+        let request = handle.join().unwrap();
+        let expected = base64::encode("us@r:p:ss%");
+        assert!(request.contains(&format!("Proxy-Authorization: Basic {}\r\n", expected)));
+    }
+
+    #[test]
+    fn only_http_proxies() {
+        let url = url::Url::parse("wss://cerberus-xxxx.lb.slack-msgs.com/websocket").unwrap();
+        for proxy in &["socks5://127.0.0.1:1080", "https://127.0.0.1:3128"] {
+            let proxy = url::Url::parse(proxy).unwrap();
+            match connect(&url, Some(&proxy), None) {
+                Err(Error::Internal(msg)) => assert!(msg.contains("proxy scheme"), "{}", msg),
+                r => panic!("unexpected result: {:?}", r.map(|_| ())),
+            }
+        }
+    }
+
+    #[test]
+    fn tunnel_response_too_long() {
+        let padding = "a".repeat(9000);
+        let (url, handle) = proxy(&format!(
+            "HTTP/1.1 200 OK\r\nX-Padding: {}\r\n\r\n",
+            padding
+        ));
+        let mut tcp = TcpStream::connect(host_and_port(&url).unwrap()).unwrap();
+        let err = tunnel(&mut tcp, &url, "example.com", 443).unwrap_err();
+        drop(tcp);
+        let _ = handle.join();
+        match err {
+            Error::Internal(msg) => assert!(msg.contains("longer than 8192 bytes"), "{}", msg),
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn tunnel_refused() {
+        let (url, handle) = proxy("HTTP/1.1 407 Proxy Authentication Required\r\n\r\n");
+        let mut tcp = TcpStream::connect(host_and_port(&url).unwrap()).unwrap();
+        let err = tunnel(&mut tcp, &url, "example.com", 443).unwrap_err();
+        handle.join().unwrap();
+        match err {
+            Error::Internal(msg) => assert!(msg.contains("407")),
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Client-side pacing of messages sent to a channel.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Per-channel token bucket limiting how fast `Sender::send_message` posts,
+/// see `RunConfig::throttle`.
+///
+/// Slack allows roughly one message per second per channel and disconnects
+/// clients that keep exceeding it.
+#[derive(Clone, Copy, Debug)]
+pub struct Throttle {
+    /// Time to regain one message of allowance in a channel
+    pub interval: Duration,
+    /// Messages that may be sent to a channel back to back before pacing kicks in
+    pub burst: u32,
+}
+
+impl Default for Throttle {
+    fn default() -> Throttle {
+        Throttle {
+            interval: Duration::from_secs(1),
+            burst: 1,
+        }
+    }
+}
+
+/// The buckets of every channel sent to so far
+#[derive(Debug)]
+pub(crate) struct Buckets {
+    throttle: Throttle,
+    // tokens left and when they were last refilled
+    channels: HashMap<String, (f64, Instant)>,
+}
+
+impl Buckets {
+    pub fn new(throttle: Throttle) -> Buckets {
+        Buckets {
+            throttle,
+            channels: HashMap::new(),
+        }
+    }
+
+    /// Takes a token for `channel`, returning how long to wait before sending.
+    ///
+    /// The token is taken even if none is available yet, so concurrent
+    /// senders queue up behind each other.
+    pub fn reserve(&mut self, channel: &str, now: Instant) -> Duration {
+        let burst = f64::from(self.throttle.burst.max(1));
+        let interval = self.throttle.interval.as_secs_f64();
+        let bucket = self
+            .channels
+            .entry(channel.to_string())
+            .or_insert((burst, now));
+        if interval > 0.0 {
+            let refill = now.saturating_duration_since(bucket.1).as_secs_f64() / interval;
+            bucket.0 = (bucket.0 + refill).min(burst);
+        } else {
+            bucket.0 = burst;
+        }
+        bucket.1 = now;
+        bucket.0 -= 1.0;
+        if bucket.0 >= 0.0 {
+            Duration::from_secs(0)
+        } else {
+            self.throttle.interval.mul_f64(-bucket.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn burst_then_paced() {
+        let mut buckets = Buckets::new(Throttle {
+            interval: Duration::from_secs(1),
+            burst: 2,
+        });
+        let now = Instant::now();
+        assert_eq!(buckets.reserve("C1", now), Duration::from_secs(0));
+        assert_eq!(buckets.reserve("C1", now), Duration::from_secs(0));
+        assert_eq!(buckets.reserve("C1", now), Duration::from_secs(1));
+        assert_eq!(buckets.reserve("C1", now), Duration::from_secs(2));
+        // other channels have their own allowance
+        assert_eq!(buckets.reserve("C2", now), Duration::from_secs(0));
+    }
+
+    #[test]
+    fn refills_over_time() {
+        let mut buckets = Buckets::new(Throttle::default());
+        let now = Instant::now();
+        assert_eq!(buckets.reserve("C1", now), Duration::from_secs(0));
+        let later = now + Duration::from_millis(500);
+        assert_eq!(buckets.reserve("C1", later), Duration::from_millis(500));
+        let much_later = later + Duration::from_secs(10);
+        assert_eq!(buckets.reserve("C1", much_later), Duration::from_secs(0));
+    }
+}
@@ -0,0 +1,139 @@
+//
+// Copyright 2015-2016 the slack-rs authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Exact parsing and conversions for `api::Timestamp`
+
+use crate::api::Timestamp;
+use serde::{Deserialize, Deserializer};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Conversions `slack_api`'s `Timestamp` is missing.
+///
+/// `Timestamp` already orders chronologically and displays in Slack's
+/// `seconds.micros` form.
+pub trait TimestampExt: Sized {
+    /// Parses the `seconds.micros` form Slack uses for `ts` values, keeping
+    /// every digit. Going through `f64`, as `From<f64>` and
+    /// `From<(u64, f64)>` do, can be off by a microsecond.
+    fn parse(ts: &str) -> Option<Self>;
+    /// Microseconds since the unix epoch
+    fn as_micros(&self) -> u64;
+    /// The point in time the timestamp denotes
+    fn to_system_time(&self) -> SystemTime;
+}
+
+impl TimestampExt for Timestamp {
+    fn parse(ts: &str) -> Option<Timestamp> {
+        let (secs, frac) = match ts.find('.') {
+            Some(dot) => (&ts[..dot], &ts[dot + 1..]),
+            None => (ts, ""),
+        };
+        let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        if secs.is_empty() || !digits(secs) || frac.len() > 6 || !digits(frac) {
+            return None;
+        }
+        let micros = format!("{:0<6}", frac).parse::<u64>().ok()?;
+        Some(from_parts(secs.parse().ok()?, micros))
+    }
+
+    fn as_micros(&self) -> u64 {
+        // `Display` is the only way to the inner value
+        self.to_string().replace('.', "").parse().unwrap_or(0)
+    }
+
+    fn to_system_time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_micros(self.as_micros())
+    }
+}
+
+/// Builds a `Timestamp` from whole seconds and microseconds, exactly
+pub(crate) fn from_parts(secs: u64, micros: u64) -> Timestamp {
+    // `From<(u64, f64)>` truncates `fraction * 1e6`; aiming at the middle of
+    // the microsecond keeps float error from landing on the one below
+    Timestamp::from((secs, (micros as f64 + 0.5) / 1_000_000.0))
+}
+
+/// Deserializes a `seconds.micros` string with `TimestampExt::parse`, for
+/// `#[serde(deserialize_with)]`
+pub(crate) fn exact<'de, D>(deserializer: D) -> Result<Timestamp, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let ts = String::deserialize(deserializer)?;
+    Timestamp::parse(&ts)
+        .ok_or_else(|| serde::de::Error::custom(format!("invalid timestamp `{}`", ts)))
+}
+
+/// `exact` for an optional field; also needs `#[serde(default)]`
+pub(crate) fn exact_opt<'de, D>(deserializer: D) -> Result<Option<Timestamp>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Exact(#[serde(deserialize_with = "exact")] Timestamp);
+    Ok(Option::<Exact>::deserialize(deserializer)?.map(|Exact(ts)| ts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_round_trips() {
+        for ts in &[
+            "1588859442.008705",
+            "1588859442.000249",
+            "1355517523.000008",
+        ] {
+            assert_eq!(Timestamp::parse(ts).unwrap().to_string(), *ts);
+        }
+        assert_eq!(
+            Timestamp::parse("1588859442").unwrap().to_string(),
+            "1588859442.000000"
+        );
+        assert_eq!(
+            Timestamp::parse("1588859442.1").unwrap().to_string(),
+            "1588859442.100000"
+        );
+    }
+
+    #[test]
+    fn parse_rejects_garbage() {
+        for ts in &["", ".5", "abc", "12.3.4", "1588859442.0000001", "-1.5"] {
+            assert!(Timestamp::parse(ts).is_none(), "{}", ts);
+        }
+    }
+
+    #[test]
+    fn every_microsecond_is_exact() {
+        for micros in (0..1_000_000).step_by(7) {
+            assert_eq!(
+                from_parts(1588859442, micros).as_micros() % 1_000_000,
+                micros
+            );
+        }
+    }
+
+    #[test]
+    fn orders_and_converts() {
+        let earlier = Timestamp::parse("1588859442.000249").unwrap();
+        let later = Timestamp::parse("1588859442.000250").unwrap();
+        assert!(earlier < later);
+        assert_eq!(
+            later.to_system_time(),
+            UNIX_EPOCH + Duration::new(1588859442, 250_000)
+        );
+    }
+}